@@ -15,6 +15,10 @@
 
 //! Axis-aligned bounding boxes
 
+use std::from_str::{FromStr, from_str};
+
+use approx::{RelativeEq, UlpsEq, default_epsilon};
+use format::{ToStrDigits, format_list, split_list};
 use point::{Point2, Point3};
 use vector::{Vec2, Vec3};
 
@@ -29,3 +33,9 @@ pub struct Aabb3<S> {
     center: Point3<S>,
     size: Vec3<S>,
 }
+
+approx_eq_fields!(impl<S> Aabb2<S> { center, size })
+approx_eq_fields!(impl<S> Aabb3<S> { center, size })
+
+format_fields!(impl<S> Aabb2 { center, size })
+format_fields!(impl<S> Aabb3 { center, size })
@@ -19,8 +19,12 @@ pub use std::num::{cast, zero};
 pub use std::num::{sinh, cosh, tanh};
 pub use std::num::{asinh, acosh, atanh};
 
+use std::from_str::{FromStr, from_str};
 use std::num::Zero;
 
+use approx::{RelativeEq, UlpsEq, default_epsilon};
+use format::ToStrDigits;
+
 #[deriving(Clone, Eq, Ord, Zero)] pub struct Rad<S> { s: S }
 #[deriving(Clone, Eq, Ord, Zero)] pub struct Deg<S> { s: S }
 
@@ -180,41 +184,34 @@ impl<S: Float> Angle<S> for Deg<S> {
 #[inline] pub fn atan<S: Float, A: Angle<S>>(s: S) -> A { Angle::from(rad(s.atan())) }
 #[inline] pub fn atan2<S: Float, A: Angle<S>>(a: S, b: S) -> A { Angle::from(rad(a.atan2(&b))) }
 
-impl<S: Float> ToStr for Rad<S> { fn to_str(&self) -> ~str { fmt!("%? rad", self.s) } }
-impl<S: Float> ToStr for Deg<S> { fn to_str(&self) -> ~str { fmt!("%?°", self.s) } }
+impl<S: ToStr> ToStr for Rad<S> { fn to_str(&self) -> ~str { fmt!("%s rad", self.s.to_str()) } }
+impl<S: ToStr> ToStr for Deg<S> { fn to_str(&self) -> ~str { fmt!("%s°", self.s.to_str()) } }
 
-impl<S: Float> ApproxEq<S> for Rad<S> {
-    #[inline]
-    fn approx_epsilon() -> S {
-        // TODO: fix this after static methods are fixed in rustc
-        fail!(~"Doesn't work!");
-    }
+impl<S: ToStrDigits> ToStrDigits for Rad<S> { fn to_str_digits(&self, digits: uint) -> ~str { fmt!("%s rad", self.s.to_str_digits(digits)) } }
+impl<S: ToStrDigits> ToStrDigits for Deg<S> { fn to_str_digits(&self, digits: uint) -> ~str { fmt!("%s°", self.s.to_str_digits(digits)) } }
 
-    #[inline]
-    fn approx_eq(&self, other: &Rad<S>) -> bool {
-        self.s.approx_eq(&other.s)
-    }
-
-    #[inline]
-    fn approx_eq_eps(&self, other: &Rad<S>, approx_epsilon: &S) -> bool {
-        self.s.approx_eq_eps(&other.s, approx_epsilon)
+impl<S: FromStr> FromStr for Rad<S> {
+    fn from_str(s: &str) -> Option<Rad<S>> {
+        let s = s.trim();
+        if s.ends_with("rad") {
+            from_str(s.slice_to(s.len() - "rad".len()).trim()).map(|s| Rad { s: s })
+        } else {
+            None
+        }
     }
 }
 
-impl<S: Float> ApproxEq<S> for Deg<S> {
-    #[inline]
-    fn approx_epsilon() -> S {
-        // TODO: fix this after static methods are fixed in rustc
-        fail!(~"Doesn't work!");
+impl<S: FromStr> FromStr for Deg<S> {
+    fn from_str(s: &str) -> Option<Deg<S>> {
+        let s = s.trim();
+        if s.ends_with("°") {
+            from_str(s.slice_to(s.len() - "°".len()).trim()).map(|s| Deg { s: s })
+        } else {
+            None
+        }
     }
+}
 
-    #[inline]
-    fn approx_eq(&self, other: &Deg<S>) -> bool {
-        self.s.approx_eq(&other.s)
-    }
+approx_eq_fields!(impl<S> Rad<S> { s })
 
-    #[inline]
-    fn approx_eq_eps(&self, other: &Deg<S>, approx_epsilon: &S) -> bool {
-        self.s.approx_eq_eps(&other.s, approx_epsilon)
-    }
-}
+approx_eq_fields!(impl<S> Deg<S> { s })
@@ -0,0 +1,211 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Approximate equality comparisons for floating point types
+//!
+//! In addition to the absolute tolerance of the standard `ApproxEq` trait,
+//! the types in this library support comparisons with a tolerance relative
+//! to the magnitude of the values being compared, and comparisons that count
+//! the number of representable floating point values between them.
+//!
+//! The `assert_relative_eq!` and `assert_ulps_eq!` macros check these
+//! comparisons, and print both values when they fail.
+
+#[macro_escape];
+
+use std::cast::transmute;
+
+/// The default absolute tolerance returned by `approx_epsilon`, which is the
+/// machine epsilon of the scalar type: the difference between one and the
+/// next representable value. This scales with the precision of the type, so
+/// it is much smaller for `f64` than for `f32`.
+///
+/// The `approx_eq` methods keep the fixed tolerance of the standard library's
+/// `ApproxEq` implementations for the built-in floating point types.
+#[inline]
+pub fn default_epsilon<S: Float>() -> S {
+    Float::epsilon()
+}
+
+/// Comparisons with a tolerance relative to the size of the values being
+/// compared, which remain meaningful for values that are much larger or
+/// smaller than one.
+pub trait RelativeEq<S> {
+    /// Returns `true` if every component of `self` and `other` differs by at
+    /// most `epsilon`, or by at most `max_relative` times the larger of the
+    /// two magnitudes. The absolute tolerance handles values near zero, where
+    /// a relative tolerance becomes too strict to be useful.
+    fn relative_eq(&self, other: &Self, epsilon: &S, max_relative: &S) -> bool;
+}
+
+/// Comparisons measured in units in the last place (ULPs): the number of
+/// representable floating point values between two values.
+pub trait UlpsEq {
+    /// Returns `true` if every component of `self` and `other` are at most
+    /// `max_ulps` representable values apart. Values with different signs are
+    /// only equal if they are both zero, and `NaN` is never equal to anything.
+    fn ulps_eq(&self, other: &Self, max_ulps: uint) -> bool;
+}
+
+/// Fails if `given.relative_eq(&expected, &epsilon, &max_relative)` is
+/// `false`, printing both values.
+macro_rules! assert_relative_eq(
+    ($given:expr, $expected:expr, $epsilon:expr, $max_relative:expr) => ({
+        let given_val = $given;
+        let expected_val = $expected;
+        if !given_val.relative_eq(&expected_val, &$epsilon, &$max_relative) {
+            fail!("left: %? is not relatively equal to right: %?", given_val, expected_val);
+        }
+    })
+)
+
+/// Fails if `given.ulps_eq(&expected, max_ulps)` is `false`, printing both
+/// values.
+macro_rules! assert_ulps_eq(
+    ($given:expr, $expected:expr, $max_ulps:expr) => ({
+        let given_val = $given;
+        let expected_val = $expected;
+        if !given_val.ulps_eq(&expected_val, $max_ulps) {
+            fail!("left: %? is not within %? ulps of right: %?", given_val, $max_ulps, expected_val);
+        }
+    })
+)
+
+macro_rules! float_approx_eq(
+    ($T:ty, $Bits:ty) => (
+        impl RelativeEq<$T> for $T {
+            #[inline]
+            fn relative_eq(&self, other: &$T, epsilon: &$T, max_relative: &$T) -> bool {
+                let diff = (*self - *other).abs();
+                if diff <= *epsilon { return true; }
+                let (a, b) = (self.abs(), other.abs());
+                let largest = if a > b { a } else { b };
+                diff <= largest * *max_relative
+            }
+        }
+
+        impl UlpsEq for $T {
+            #[inline]
+            fn ulps_eq(&self, other: &$T, max_ulps: uint) -> bool {
+                // this also handles positive and negative zero
+                if *self == *other { return true; }
+                if self.is_NaN() || other.is_NaN() { return false; }
+                if (*self < 0.0) != (*other < 0.0) { return false; }
+
+                // the bit patterns of floats with the same sign are ordered
+                // the same way as the floats themselves
+                let a: $Bits = unsafe { transmute(*self) };
+                let b: $Bits = unsafe { transmute(*other) };
+                let diff = if a > b { a - b } else { b - a };
+                diff as uint <= max_ulps
+            }
+        }
+    )
+)
+
+float_approx_eq!(f32, i32)
+float_approx_eq!(f64, i64)
+
+impl RelativeEq<float> for float {
+    #[inline]
+    fn relative_eq(&self, other: &float, epsilon: &float, max_relative: &float) -> bool {
+        (*self as f64).relative_eq(&(*other as f64), &(*epsilon as f64), &(*max_relative as f64))
+    }
+}
+
+impl UlpsEq for float {
+    #[inline]
+    fn ulps_eq(&self, other: &float, max_ulps: uint) -> bool {
+        (*self as f64).ulps_eq(&(*other as f64), max_ulps)
+    }
+}
+
+/// Implements the approximate equality traits for a type that implements
+/// `Array`, by comparing each of its elements.
+macro_rules! approx_eq(
+    (impl<$S:ident> $Self:ty) => (
+        impl<$S: Float> ApproxEq<$S> for $Self {
+            #[inline]
+            fn approx_epsilon() -> $S {
+                default_epsilon()
+            }
+
+            #[inline]
+            fn approx_eq(&self, other: &$Self) -> bool {
+                self.iter().zip(other.iter())
+                           .all(|(a, b)| a.approx_eq(b))
+            }
+
+            #[inline]
+            fn approx_eq_eps(&self, other: &$Self, approx_epsilon: &$S) -> bool {
+                self.iter().zip(other.iter())
+                           .all(|(a, b)| a.approx_eq_eps(b, approx_epsilon))
+            }
+        }
+
+        impl<$S: Float + RelativeEq<$S>> RelativeEq<$S> for $Self {
+            #[inline]
+            fn relative_eq(&self, other: &$Self, epsilon: &$S, max_relative: &$S) -> bool {
+                self.iter().zip(other.iter())
+                           .all(|(a, b)| a.relative_eq(b, epsilon, max_relative))
+            }
+        }
+
+        impl<$S: Float + UlpsEq> UlpsEq for $Self {
+            #[inline]
+            fn ulps_eq(&self, other: &$Self, max_ulps: uint) -> bool {
+                self.iter().zip(other.iter())
+                           .all(|(a, b)| a.ulps_eq(b, max_ulps))
+            }
+        }
+    )
+)
+
+/// Implements the approximate equality traits for a struct, by comparing
+/// each of the listed fields.
+macro_rules! approx_eq_fields(
+    (impl<$S:ident> $Self:ty { $($field:ident),+ }) => (
+        impl<$S: Float> ApproxEq<$S> for $Self {
+            #[inline]
+            fn approx_epsilon() -> $S {
+                default_epsilon()
+            }
+
+            #[inline]
+            fn approx_eq(&self, other: &$Self) -> bool {
+                $(self.$field.approx_eq(&other.$field))&&+
+            }
+
+            #[inline]
+            fn approx_eq_eps(&self, other: &$Self, approx_epsilon: &$S) -> bool {
+                $(self.$field.approx_eq_eps(&other.$field, approx_epsilon))&&+
+            }
+        }
+
+        impl<$S: Float + RelativeEq<$S>> RelativeEq<$S> for $Self {
+            #[inline]
+            fn relative_eq(&self, other: &$Self, epsilon: &$S, max_relative: &$S) -> bool {
+                $(self.$field.relative_eq(&other.$field, epsilon, max_relative))&&+
+            }
+        }
+
+        impl<$S: Float + UlpsEq> UlpsEq for $Self {
+            #[inline]
+            fn ulps_eq(&self, other: &$Self, max_ulps: uint) -> bool {
+                $(self.$field.ulps_eq(&other.$field, max_ulps))&&+
+            }
+        }
+    )
+)
@@ -0,0 +1,228 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! GPU buffer layouts
+//!
+//! `Array::as_slice` gives the tightly packed representation of a value, but
+//! the `std140` and `std430` layouts used by GLSL uniform and storage blocks
+//! pad some types: a `vec3` is aligned to 16 bytes, every column of a matrix
+//! starts on a multiple of the column alignment, and in `std140` the elements
+//! of an array are padded to 16 bytes. `LayoutWriter` applies these rules
+//! while packing values into a byte buffer, in the native byte order.
+
+use std::cast::transmute;
+use std::sys::size_of;
+
+use array::Array;
+use matrix::{Mat2, Mat3, Mat4};
+use matrix::{Mat2x3, Mat2x4, Mat3x2, Mat3x4, Mat4x2, Mat4x3};
+use vector::{Vec2, Vec3, Vec4};
+
+/// The layout rules of a GLSL interface block.
+#[deriving(Clone, Eq)]
+pub enum Layout {
+    /// The layout of `layout(std140)` blocks.
+    Std140,
+    /// The layout of `layout(std430)` blocks. This is the same as `std140`,
+    /// except that arrays are not padded to the alignment of a `vec4`.
+    Std430,
+}
+
+impl Layout {
+    /// The alignment of an array whose elements have the alignment `align`.
+    pub fn array_alignment(&self, align: uint) -> uint {
+        match *self {
+            Std140 => round_up(align, 16),
+            Std430 => align,
+        }
+    }
+
+    /// The distance in bytes between the starts of consecutive elements of an
+    /// array, whose elements have the alignment `align` and size `size`.
+    pub fn array_stride(&self, align: uint, size: uint) -> uint {
+        round_up(size, self.array_alignment(align))
+    }
+}
+
+#[inline]
+fn round_up(x: uint, align: uint) -> uint {
+    (x + align - 1) / align * align
+}
+
+/// A value that can be written to an interface block.
+pub trait BufferData {
+    /// The base alignment of the value, in bytes.
+    fn alignment(&self, layout: Layout) -> uint;
+
+    /// The number of bytes the value occupies, not including any padding
+    /// that follows it.
+    fn size(&self, layout: Layout) -> uint;
+
+    /// Write the value at the current offset of `w`, which is already
+    /// aligned to `self.alignment(w.layout)`.
+    fn write_aligned(&self, w: &mut LayoutWriter);
+}
+
+macro_rules! buffer_scalar(
+    ($T:ty, $n:expr) => (
+        impl BufferData for $T {
+            #[inline] fn alignment(&self, _: Layout) -> uint { size_of::<$T>() }
+            #[inline] fn size(&self, _: Layout) -> uint { size_of::<$T>() }
+
+            #[inline]
+            fn write_aligned(&self, w: &mut LayoutWriter) {
+                let bytes: [u8, ..$n] = unsafe { transmute(*self) };
+                w.write_bytes(bytes);
+            }
+        }
+    )
+)
+
+buffer_scalar!(f32, 4)
+buffer_scalar!(f64, 8)
+buffer_scalar!(i32, 4)
+buffer_scalar!(u32, 4)
+
+/// Implements `BufferData` for a vector type with `n` components, which is
+/// aligned to `align` times the size of a component.
+macro_rules! buffer_vector(
+    (impl<$S:ident> $Self:ty, $n:expr, $align:expr) => (
+        impl<$S: Clone + BufferData> BufferData for $Self {
+            #[inline] fn alignment(&self, layout: Layout) -> uint { $align * self.x.size(layout) }
+            #[inline] fn size(&self, layout: Layout) -> uint { $n * self.x.size(layout) }
+
+            #[inline]
+            fn write_aligned(&self, w: &mut LayoutWriter) {
+                for x in self.iter() { x.write_aligned(w); }
+            }
+        }
+    )
+)
+
+buffer_vector!(impl<S> Vec2<S>, 2, 2)
+buffer_vector!(impl<S> Vec3<S>, 3, 4)
+buffer_vector!(impl<S> Vec4<S>, 4, 4)
+
+/// Implements `BufferData` for a column major matrix type with `n` columns,
+/// which is laid out as an array of its columns.
+macro_rules! buffer_matrix(
+    (impl<$S:ident> $Self:ty, $n:expr) => (
+        impl<$S: Clone + BufferData> BufferData for $Self {
+            #[inline]
+            fn alignment(&self, layout: Layout) -> uint {
+                layout.array_alignment(self.x.alignment(layout))
+            }
+
+            #[inline]
+            fn size(&self, layout: Layout) -> uint {
+                $n * layout.array_stride(self.x.alignment(layout), self.x.size(layout))
+            }
+
+            fn write_aligned(&self, w: &mut LayoutWriter) {
+                let stride = w.layout.array_stride(self.x.alignment(w.layout), self.x.size(w.layout));
+                for c in self.iter() {
+                    let start = w.offset();
+                    c.write_aligned(w);
+                    w.pad_to(start + stride);
+                }
+            }
+        }
+    )
+)
+
+buffer_matrix!(impl<S> Mat2<S>, 2)
+buffer_matrix!(impl<S> Mat3<S>, 3)
+buffer_matrix!(impl<S> Mat4<S>, 4)
+
+buffer_matrix!(impl<S> Mat2x3<S>, 2)
+buffer_matrix!(impl<S> Mat2x4<S>, 2)
+buffer_matrix!(impl<S> Mat3x2<S>, 3)
+buffer_matrix!(impl<S> Mat3x4<S>, 3)
+buffer_matrix!(impl<S> Mat4x2<S>, 4)
+buffer_matrix!(impl<S> Mat4x3<S>, 4)
+
+/// Packs values into a byte buffer, following the rules of a block layout.
+/// Any padding is filled with zeros.
+pub struct LayoutWriter {
+    layout: Layout,
+    bytes: ~[u8],
+}
+
+impl LayoutWriter {
+    /// Create an empty buffer.
+    pub fn new(layout: Layout) -> LayoutWriter {
+        LayoutWriter { layout: layout, bytes: ~[] }
+    }
+
+    /// The offset in bytes at which the next value will be written, before
+    /// it is aligned.
+    #[inline]
+    pub fn offset(&self) -> uint { self.bytes.len() }
+
+    /// Pad the buffer with zeros up to `offset`. Does nothing if the buffer is
+    /// already at least that long.
+    pub fn pad_to(&mut self, offset: uint) {
+        while self.bytes.len() < offset { self.bytes.push(0u8); }
+    }
+
+    /// Pad the buffer with zeros up to the next multiple of `align`.
+    pub fn align(&mut self, align: uint) {
+        let offset = round_up(self.offset(), align);
+        self.pad_to(offset);
+    }
+
+    /// Append raw bytes, without any alignment.
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.push_all(bytes);
+    }
+
+    /// Align the buffer for `value` and write it.
+    ///
+    /// # Return value
+    ///
+    /// The offset the value was written at.
+    pub fn write<T: BufferData>(&mut self, value: &T) -> uint {
+        let align = value.alignment(self.layout);
+        self.align(align);
+        let offset = self.offset();
+        value.write_aligned(self);
+        offset
+    }
+
+    /// Align the buffer for an array of `values` and write them, with each
+    /// element padded to the array stride.
+    ///
+    /// # Return value
+    ///
+    /// The offset the array was written at.
+    pub fn write_array<T: BufferData>(&mut self, values: &[T]) -> uint {
+        if values.is_empty() { return self.offset(); }
+
+        let align = values[0].alignment(self.layout);
+        let stride = self.layout.array_stride(align, values[0].size(self.layout));
+        let array_align = self.layout.array_alignment(align);
+        self.align(array_align);
+        let offset = self.offset();
+        for value in values.iter() {
+            let start = self.offset();
+            value.write_aligned(self);
+            self.pad_to(start + stride);
+        }
+        offset
+    }
+
+    /// Take the contents of the buffer.
+    pub fn unwrap(self) -> ~[u8] { self.bytes }
+}
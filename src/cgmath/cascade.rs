@@ -0,0 +1,156 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cascaded shadow maps
+//!
+//! Cascaded shadow mapping splits the view frustum of the camera into a
+//! number of slices along the view direction, and renders a separate shadow
+//! map for each slice, so that the shadows close to the viewer have a higher
+//! resolution than those in the distance.
+
+use std::num::{zero, one, cast};
+use std::vec;
+
+use angle::Angle;
+use matrix::{Mat4, Matrix};
+use projection::{Ortho, PerspectiveFov};
+use vector::{Vec3, Vec4, Vector, EuclideanVector};
+
+/// The distribution of the split distances between the cascades.
+#[deriving(Clone, Eq)]
+pub enum SplitScheme<S> {
+    /// The cascades are of equal depth.
+    Uniform,
+    /// The depth of each cascade increases geometrically, so that every
+    /// cascade covers the same ratio of near to far distance.
+    Logarithmic,
+    /// The split distances are a blend of the uniform and logarithmic
+    /// distances, where a factor of `0` is uniform and `1` is logarithmic.
+    /// See _Parallel-Split Shadow Maps for Large-scale Virtual Environments_,
+    /// Zhang et al.
+    Practical(S),
+}
+
+/// Compute the view space distances that split the range from `near` to
+/// `far` into `count` cascades.
+///
+/// # Return value
+///
+/// The `count + 1` split distances, starting at `near` and ending at `far`.
+pub fn split_distances<S: Float>(near: S, far: S, count: uint, scheme: SplitScheme<S>) -> ~[S] {
+    let n: S = cast(count).unwrap();
+    let ratio_ln = (far / near).ln();
+    do vec::from_fn(count + 1) |i| {
+        let t: S = cast::<uint, S>(i).unwrap() / n;
+        let uniform = near + (far - near) * t;
+        let log = near * (ratio_ln * t).exp();
+        match scheme {
+            Uniform => uniform,
+            Logarithmic => log,
+            Practical(ref lambda) => uniform + (log - uniform) * *lambda,
+        }
+    }
+}
+
+/// The light space projection for a single cascade.
+#[deriving(Clone, Eq)]
+pub struct Cascade<S> {
+    /// The view space distance to the start of the cascade.
+    near: S,
+    /// The view space distance to the end of the cascade.
+    far: S,
+    /// Transforms from world space to the light's view space.
+    view: Mat4<S>,
+    /// The orthographic projection of the light, in the light's view space.
+    proj: Ortho<S>,
+}
+
+/// Split the view frustum of a camera into cascades, and compute a light
+/// space orthographic projection enclosing each of them.
+///
+/// # Arguments
+///
+/// - `camera`: the projection of the camera
+/// - `camera_to_world`: the transform from the camera's view space to world
+///   space, which is the inverse of the camera's view matrix
+/// - `light_dir`: the direction that the light is travelling in
+/// - `count`: the number of cascades
+/// - `scheme`: the distribution of the split distances
+/// - `resolution`: the width and height of each shadow map, in texels
+///
+/// # Stabilization
+///
+/// Each projection is fitted around the bounding sphere of its slice of the
+/// frustum rather than the slice itself, so that its size does not change as
+/// the camera rotates, and its position is snapped to whole shadow map texels,
+/// so that it does not shift by fractions of a texel as the camera moves.
+/// Both of these prevent the edges of shadows from shimmering.
+///
+/// The near plane of each projection only just encloses the bounding sphere,
+/// so casters outside of the sphere in the direction of the light are
+/// clipped away. Moving `proj.near` further back, or clamping depth when
+/// rendering the shadow map, avoids this.
+pub fn cascades<S: Float, A: Angle<S>>(camera: &PerspectiveFov<S, A>, camera_to_world: &Mat4<S>,
+                                       light_dir: &Vec3<S>, count: uint, scheme: SplitScheme<S>,
+                                       resolution: uint) -> ~[Cascade<S>] {
+    let view = light_view(light_dir);
+    let splits = split_distances(camera.near.clone(), camera.far.clone(), count, scheme);
+    do vec::from_fn(count) |i| {
+        let slice = PerspectiveFov {
+            fovy:   camera.fovy.clone(),
+            aspect: camera.aspect.clone(),
+            near:   splits[i].clone(),
+            far:    splits[i + 1].clone(),
+        };
+        let sphere = slice.to_perspective().to_points().transform(camera_to_world).bounding_sphere();
+
+        // quantize the radius so that floating point error does not change
+        // the size of the projection from frame to frame
+        let quantum: S = cast(16).unwrap();
+        let radius = (sphere.radius * quantum).ceil() / quantum;
+        let texel = radius * cast(2).unwrap() / cast(resolution).unwrap();
+
+        let c = view.mul_v(&Vec4::new(sphere.center.x.clone(), sphere.center.y.clone(),
+                                      sphere.center.z.clone(), one()));
+        let x = (c.x / texel).floor() * texel;
+        let y = (c.y / texel).floor() * texel;
+
+        Cascade {
+            near: splits[i].clone(),
+            far:  splits[i + 1].clone(),
+            view: view.clone(),
+            proj: Ortho {
+                left:   x - radius,
+                right:  x + radius,
+                bottom: y - radius,
+                top:    y + radius,
+                near:   -c.z - radius,
+                far:    -c.z + radius,
+            },
+        }
+    }
+}
+
+/// A rotation from world space to a view space looking along `dir`.
+fn light_view<S: Float>(dir: &Vec3<S>) -> Mat4<S> {
+    let forward = dir.normalize();
+    let up = if forward.y.abs() > cast(0.99).unwrap() { Vec3::unit_z() } else { Vec3::unit_y() };
+    let side = forward.cross(&up).normalize();
+    let up = side.cross(&forward);
+    Mat4::new(side.x.clone(), up.x.clone(), -forward.x, zero(),
+              side.y.clone(), up.y.clone(), -forward.y, zero(),
+              side.z.clone(), up.z.clone(), -forward.z, zero(),
+              zero(), zero(), zero(), one())
+}
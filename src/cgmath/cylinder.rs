@@ -15,6 +15,7 @@
 
 //! Oriented bounding cylinder
 
+use approx::{RelativeEq, UlpsEq, default_epsilon};
 use point::Point3;
 use vector::Vec3;
 
@@ -24,3 +25,5 @@ pub struct Cylinder<S> {
     axis: Vec3<S>,
     radius: S,
 }
+
+approx_eq_fields!(impl<S> Cylinder<S> { center, axis, radius })
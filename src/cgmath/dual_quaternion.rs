@@ -0,0 +1,277 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Dual quaternions for representing rigid transforms.
+
+use std::from_str::{FromStr, from_str};
+use std::num::{zero, one, cast};
+
+use approx::{RelativeEq, UlpsEq, default_epsilon};
+use format::{ToStrDigits, format_list, split_list};
+use matrix::{Matrix, Mat3, Mat4, ToMat3, ToMat4};
+use point::Point3;
+use quaternion::{Quat, ToQuat};
+use vector::{Vec3, Vec4, Vector, EuclideanVector};
+
+/// A dual quaternion of the form `real + ε dual`, where `ε² = 0`.
+///
+/// A unit dual quaternion represents a rigid transform, that is, a rotation
+/// followed by a translation. Unlike a matrix, blending several unit dual
+/// quaternions and renormalizing produces another rigid transform, which
+/// makes them well suited to skinning.
+#[deriving(Clone, Eq)]
+pub struct DualQuat<S> { real: Quat<S>, dual: Quat<S> }
+
+impl<S: Float> DualQuat<S> {
+    /// Construct a new dual quaternion from its real and dual parts
+    #[inline]
+    pub fn new(real: Quat<S>, dual: Quat<S>) -> DualQuat<S> {
+        DualQuat { real: real, dual: dual }
+    }
+
+    /// The multiplicative identity, ie: the transform that does nothing
+    #[inline]
+    pub fn identity() -> DualQuat<S> {
+        DualQuat::new(Quat::identity(), Quat::zero())
+    }
+
+    /// Create a rigid transform that applies the rotation `rot`, followed by
+    /// the translation `disp`. The rotation should be normalized.
+    #[inline]
+    pub fn from_rotation_translation(rot: &Quat<S>, disp: &Vec3<S>) -> DualQuat<S> {
+        let half: S = cast(0.5).unwrap();
+        DualQuat::new(rot.clone(),
+                      Quat::from_sv(zero(), disp.clone()).mul_q(rot).mul_s(half))
+    }
+
+    /// Create a rigid transform from a rotation
+    #[inline]
+    pub fn from_rotation(rot: &Quat<S>) -> DualQuat<S> {
+        DualQuat::new(rot.clone(), Quat::zero())
+    }
+
+    /// Create a rigid transform from a translation
+    #[inline]
+    pub fn from_translation(disp: &Vec3<S>) -> DualQuat<S> {
+        DualQuat::from_rotation_translation(&Quat::identity(), disp)
+    }
+
+    /// Create a rigid transform from a homogeneous transformation matrix. The
+    /// upper 3 x 3 portion of the matrix should be a rotation.
+    pub fn from_mat4(mat: &Mat4<S>) -> DualQuat<S> {
+        let rot = Mat3::new(mat.cr(0, 0).clone(), mat.cr(0, 1).clone(), mat.cr(0, 2).clone(),
+                            mat.cr(1, 0).clone(), mat.cr(1, 1).clone(), mat.cr(1, 2).clone(),
+                            mat.cr(2, 0).clone(), mat.cr(2, 1).clone(), mat.cr(2, 2).clone());
+        let disp = Vec3::new(mat.cr(3, 0).clone(), mat.cr(3, 1).clone(), mat.cr(3, 2).clone());
+        DualQuat::from_rotation_translation(&rot.to_quat(), &disp)
+    }
+
+    /// The rotational part of the transform
+    #[inline]
+    pub fn rotation(&self) -> Quat<S> {
+        self.real.clone()
+    }
+
+    /// The translational part of the transform
+    #[inline]
+    pub fn translation(&self) -> Vec3<S> {
+        self.dual.mul_q(&self.real.conjugate()).v.mul_s(cast(2).unwrap())
+    }
+
+    /// The result of multiplying the dual quaternion by a scalar
+    #[inline]
+    pub fn mul_s(&self, value: S) -> DualQuat<S> {
+        DualQuat::new(self.real.mul_s(value.clone()), self.dual.mul_s(value))
+    }
+
+    /// The sum of this dual quaternion and `other`
+    #[inline]
+    pub fn add_d(&self, other: &DualQuat<S>) -> DualQuat<S> {
+        DualQuat::new(self.real.add_q(&other.real), self.dual.add_q(&other.dual))
+    }
+
+    /// The result of multiplying the dual quaternion by `other`. For unit
+    /// dual quaternions this concatenates the transforms, applying `other`
+    /// first.
+    #[inline]
+    pub fn mul_d(&self, other: &DualQuat<S>) -> DualQuat<S> {
+        DualQuat::new(self.real.mul_q(&other.real),
+                      self.real.mul_q(&other.dual).add_q(&self.dual.mul_q(&other.real)))
+    }
+
+    #[inline]
+    pub fn mul_self_d(&mut self, other: &DualQuat<S>) {
+        *self = self.mul_d(other);
+    }
+
+    /// The quaternion conjugate of both parts, ie: `real* + ε dual*`. For a
+    /// unit dual quaternion this is the inverse transform.
+    #[inline]
+    pub fn conjugate(&self) -> DualQuat<S> {
+        DualQuat::new(self.real.conjugate(), self.dual.conjugate())
+    }
+
+    /// The dual number conjugate, ie: `real - ε dual`
+    #[inline]
+    pub fn dual_conjugate(&self) -> DualQuat<S> {
+        DualQuat::new(self.real.clone(), -self.dual)
+    }
+
+    /// The combination of the quaternion and dual number conjugates, ie:
+    /// `real* - ε dual*`. This is the conjugate used for transforming points.
+    #[inline]
+    pub fn combined_conjugate(&self) -> DualQuat<S> {
+        DualQuat::new(self.real.conjugate(), -self.dual.conjugate())
+    }
+
+    /// The normalized dual quaternion. The real part is scaled to unit
+    /// length, and the component of the dual part that is not orthogonal to
+    /// the real part is removed, so that the result is a rigid transform.
+    pub fn normalize(&self) -> DualQuat<S> {
+        let rlen = one::<S>() / self.real.magnitude();
+        let real = self.real.mul_s(rlen.clone());
+        let dual = self.dual.mul_s(rlen);
+        let dual = dual.sub_q(&real.mul_s(real.dot(&dual)));
+        DualQuat::new(real, dual)
+    }
+
+    #[inline]
+    pub fn normalize_self(&mut self) {
+        *self = self.normalize();
+    }
+
+    /// Transform a point by the rigid transform
+    #[inline]
+    pub fn transform_point(&self, point: &Point3<S>) -> Point3<S> {
+        Point3::from_vec(&self.transform_vector(&point.to_vec()).add_v(&self.translation()))
+    }
+
+    /// Transform a vector by the rigid transform. Vectors have no position,
+    /// so only the rotation is applied.
+    #[inline]
+    pub fn transform_vector(&self, vec: &Vec3<S>) -> Vec3<S> {
+        self.real.mul_v(vec)
+    }
+
+    /// Raise a unit dual quaternion to the power of `exponent`, scaling both
+    /// the angle and the displacement of the screw motion it represents.
+    pub fn powf(&self, exponent: S) -> DualQuat<S> {
+        let half: S = cast(0.5).unwrap();
+        let two: S = cast(2).unwrap();
+        let length = self.real.v.length();
+
+        if length.approx_eq(&zero()) {
+            // a pure translation, which scales linearly
+            DualQuat::new(Quat::identity(), self.dual.mul_s(exponent))
+        } else {
+            // decompose into the screw parameters: the angle and axis of the
+            // rotation, the displacement along the axis, and the moment of
+            // the axis about the origin
+            let angle = length.atan2(&self.real.s) * two;
+            let axis = self.real.v.div_s(length.clone());
+            let disp = -two * self.dual.s / length;
+            let moment = self.dual.v.sub_v(&axis.mul_s(disp * half * self.real.s))
+                                    .div_s(length);
+
+            let angle = angle * exponent;
+            let disp = disp * exponent;
+            let (sin, cos) = (angle * half).sin_cos();
+
+            DualQuat::new(Quat::from_sv(cos.clone(), axis.mul_s(sin.clone())),
+                          Quat::from_sv(-disp * half * sin,
+                                        moment.mul_s(sin).add_v(&axis.mul_s(disp * half * cos))))
+        }
+    }
+
+    /// Screw linear interpolation
+    ///
+    /// Interpolate between the transform and `other` with a constant
+    /// rotational and translational velocity, following the shortest path.
+    /// This is the dual quaternion equivalent of `Quat::slerp`. Both dual
+    /// quaternions should be normalized.
+    pub fn sclerp(&self, other: &DualQuat<S>, amount: S) -> DualQuat<S> {
+        let other = if self.real.dot(&other.real) < zero() { -*other } else { other.clone() };
+        self.mul_d(&self.conjugate().mul_d(&other).powf(amount))
+    }
+
+    /// Dual quaternion linear blending
+    ///
+    /// Blend a set of weighted transforms by summing and renormalizing them.
+    /// This is an efficient approximation of a weighted ScLERP, suitable for
+    /// skinning. Each transform is negated if necessary to be in the same
+    /// hemisphere as the first, so that the blend follows the shortest path.
+    ///
+    /// - [Kavan et al, Geometric Skinning with Approximate Dual Quaternion Blending]
+    ///   (http://www.seas.upenn.edu/~ladislav/kavan08geometric/kavan08geometric.pdf)
+    pub fn blend(dquats: &[DualQuat<S>], weights: &[S]) -> DualQuat<S> {
+        assert!(dquats.len() == weights.len(), "There must be one weight per dual quaternion");
+        assert!(dquats.len() > 0, "Cannot blend an empty set of dual quaternions");
+
+        let pivot = &dquats[0].real;
+        let mut sum = DualQuat::new(Quat::zero(), Quat::zero());
+        for (dq, w) in dquats.iter().zip(weights.iter()) {
+            let w = if pivot.dot(&dq.real) < zero() { -*w } else { w.clone() };
+            sum = sum.add_d(&dq.mul_s(w));
+        }
+        sum.normalize()
+    }
+}
+
+impl<S: Float> ToMat4<S> for DualQuat<S> {
+    /// Convert the dual quaternion to a homogeneous transformation matrix. The
+    /// dual quaternion should be normalized.
+    fn to_mat4(&self) -> Mat4<S> {
+        let rot = self.real.to_mat3();
+        let disp = self.translation();
+        Mat4::from_cols(Vec4::new(rot.x.x.clone(), rot.x.y.clone(), rot.x.z.clone(), zero()),
+                        Vec4::new(rot.y.x.clone(), rot.y.y.clone(), rot.y.z.clone(), zero()),
+                        Vec4::new(rot.z.x.clone(), rot.z.y.clone(), rot.z.z.clone(), zero()),
+                        Vec4::new(disp.x.clone(), disp.y.clone(), disp.z.clone(), one()))
+    }
+}
+
+impl<S: Float> Neg<DualQuat<S>> for DualQuat<S> {
+    #[inline]
+    fn neg(&self) -> DualQuat<S> {
+        DualQuat::new(-self.real, -self.dual)
+    }
+}
+
+approx_eq_fields!(impl<S> DualQuat<S> { real, dual })
+
+impl<S: Clone + ToStr> ToStr for DualQuat<S> {
+    /// Writes the dual quaternion as a list of its real and dual parts.
+    fn to_str(&self) -> ~str {
+        format_list([self.real.to_str(), self.dual.to_str()])
+    }
+}
+
+impl<S: Clone + ToStrDigits> ToStrDigits for DualQuat<S> {
+    fn to_str_digits(&self, digits: uint) -> ~str {
+        format_list([self.real.to_str_digits(digits), self.dual.to_str_digits(digits)])
+    }
+}
+
+impl<S: Clone + FromStr> FromStr for DualQuat<S> {
+    fn from_str(s: &str) -> Option<DualQuat<S>> {
+        split_list(s).and_then(|parts| {
+            if parts.len() != 2 { return None; }
+            match (from_str(parts[0]), from_str(parts[1])) {
+                (Some(real), Some(dual)) => Some(DualQuat { real: real, dual: dual }),
+                _ => None,
+            }
+        })
+    }
+}
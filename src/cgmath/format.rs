@@ -0,0 +1,221 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Formatting and parsing
+//!
+//! Vectors, points and quaternions are written as a list of their
+//! components, for example `[1, 2, 3]`, and matrices as a list of their rows,
+//! for example `[[1, 0], [0, 1]]`, so that they read the same way as they are
+//! written in mathematical notation, even though they are stored in column
+//! major order. The `FromStr` implementations accept the same format, with
+//! any amount of whitespace between the components.
+
+#[macro_escape];
+
+use std::f64;
+use std::from_str::{FromStr, from_str};
+use std::vec;
+
+/// Conversion to a string with a fixed number of digits after the decimal
+/// point.
+pub trait ToStrDigits {
+    /// Convert to a string, with every component written with exactly
+    /// `digits` digits after the decimal point.
+    fn to_str_digits(&self, digits: uint) -> ~str;
+}
+
+impl ToStrDigits for f32 {
+    #[inline]
+    fn to_str_digits(&self, digits: uint) -> ~str { f64::to_str_exact(*self as f64, digits) }
+}
+
+impl ToStrDigits for f64 {
+    #[inline]
+    fn to_str_digits(&self, digits: uint) -> ~str { f64::to_str_exact(*self, digits) }
+}
+
+impl ToStrDigits for float {
+    #[inline]
+    fn to_str_digits(&self, digits: uint) -> ~str { f64::to_str_exact(*self as f64, digits) }
+}
+
+/// Write a list of items as `[a, b, c]`.
+pub fn format_list(items: &[~str]) -> ~str {
+    fmt!("[%s]", items.connect(", "))
+}
+
+/// Write a table of items as a list of rows.
+pub fn format_rows(rows: uint, cols: uint, item: &fn(r: uint, c: uint) -> ~str) -> ~str {
+    format_list(do vec::from_fn(rows) |r| {
+        format_list(do vec::from_fn(cols) |c| { item(r, c) })
+    })
+}
+
+/// Split a list of the form `[a, b, c]` into its items, with the surrounding
+/// whitespace removed. Nested lists are returned as single items.
+///
+/// # Return value
+///
+/// The items of the list, or `None` if the string is not a list or its
+/// brackets are unbalanced.
+pub fn split_list<'a>(s: &'a str) -> Option<~[&'a str]> {
+    let s = s.trim();
+    if !s.starts_with("[") || !s.ends_with("]") || s.len() < 2 { return None; }
+
+    let inner = s.slice(1, s.len() - 1);
+    let mut items = ~[];
+    let mut depth = 0u;
+    let mut start = 0u;
+    for (i, c) in inner.char_offset_iter() {
+        match c {
+            '[' => depth += 1,
+            ']' if depth == 0 => return None,
+            ']' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(inner.slice(start, i).trim());
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    if depth != 0 { return None; }
+
+    let last = inner.slice_from(start).trim();
+    if !last.is_empty() {
+        items.push(last);
+    } else if !items.is_empty() {
+        // a trailing comma
+        return None;
+    }
+    Some(items)
+}
+
+/// Parse a list of exactly `n` scalars.
+pub fn parse_list<S: FromStr>(s: &str, n: uint) -> Option<~[S]> {
+    split_list(s).and_then(|items| {
+        if items.len() != n { return None; }
+        let mut result = vec::with_capacity(n);
+        for item in items.iter() {
+            match from_str::<S>(*item) {
+                Some(x) => result.push(x),
+                None => return None,
+            }
+        }
+        Some(result)
+    })
+}
+
+/// Parse a list of `rows` rows, each a list of `cols` scalars.
+pub fn parse_rows<S: FromStr>(s: &str, rows: uint, cols: uint) -> Option<~[~[S]]> {
+    split_list(s).and_then(|items| {
+        if items.len() != rows { return None; }
+        let mut result = vec::with_capacity(rows);
+        for item in items.iter() {
+            match parse_list::<S>(*item, cols) {
+                Some(row) => result.push(row),
+                None => return None,
+            }
+        }
+        Some(result)
+    })
+}
+
+/// Implements `ToStr`, `ToStrDigits` and `FromStr` for a type that implements
+/// `Array` with `n` scalar elements.
+macro_rules! format_array(
+    (impl<$S:ident> $Self:ty, $n:expr) => (
+        impl<$S: Clone + ToStr> ToStr for $Self {
+            fn to_str(&self) -> ~str {
+                let items: ~[~str] = self.iter().map(|x| x.to_str()).collect();
+                format_list(items)
+            }
+        }
+
+        impl<$S: Clone + ToStrDigits> ToStrDigits for $Self {
+            fn to_str_digits(&self, digits: uint) -> ~str {
+                let items: ~[~str] = self.iter().map(|x| x.to_str_digits(digits)).collect();
+                format_list(items)
+            }
+        }
+
+        impl<$S: Clone + FromStr> FromStr for $Self {
+            fn from_str(s: &str) -> Option<$Self> {
+                parse_list::<$S>(s, $n).map(|xs| Array::build(|i| xs[i].clone()))
+            }
+        }
+    )
+)
+
+/// Implements `ToStr`, `ToStrDigits` and `FromStr` for a column major matrix
+/// type with `cols` columns and `rows` rows, which are written as a list of
+/// rows.
+macro_rules! format_matrix(
+    (impl<$S:ident> $Self:ty, $cols:expr, $rows:expr) => (
+        impl<$S: Clone + ToStr> ToStr for $Self {
+            fn to_str(&self) -> ~str {
+                format_rows($rows, $cols, |r, c| self.i(c).i(r).to_str())
+            }
+        }
+
+        impl<$S: Clone + ToStrDigits> ToStrDigits for $Self {
+            fn to_str_digits(&self, digits: uint) -> ~str {
+                format_rows($rows, $cols, |r, c| self.i(c).i(r).to_str_digits(digits))
+            }
+        }
+
+        impl<$S: Clone + FromStr> FromStr for $Self {
+            fn from_str(s: &str) -> Option<$Self> {
+                parse_rows::<$S>(s, $rows, $cols).map(|rows| {
+                    Array::build(|c| Array::build(|r| rows[r][c].clone()))
+                })
+            }
+        }
+    )
+)
+
+/// Implements `ToStr`, `ToStrDigits` and `FromStr` for a struct, which is
+/// written as a list of its named fields, in the order they are listed.
+macro_rules! format_fields(
+    (impl<$S:ident> $Self:ident { $($field:ident),+ }) => (
+        impl<$S: Clone + ToStr> ToStr for $Self<$S> {
+            fn to_str(&self) -> ~str {
+                format_list([$(self.$field.to_str()),+])
+            }
+        }
+
+        impl<$S: Clone + ToStrDigits> ToStrDigits for $Self<$S> {
+            fn to_str_digits(&self, digits: uint) -> ~str {
+                format_list([$(self.$field.to_str_digits(digits)),+])
+            }
+        }
+
+        impl<$S: Clone + FromStr> FromStr for $Self<$S> {
+            fn from_str(s: &str) -> Option<$Self<$S>> {
+                split_list(s).and_then(|items| {
+                    if items.len() != [$(stringify!($field)),+].len() { return None; }
+                    let mut i = 0u;
+                    $(
+                        let $field = match from_str(items[i]) {
+                            Some(x) => x,
+                            None => return None,
+                        };
+                        i += 1;
+                    )+
+                    Some($Self { $($field: $field),+ })
+                })
+            }
+        }
+    )
+)
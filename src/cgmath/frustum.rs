@@ -15,11 +15,17 @@
 
 //! View frustum for visibility determination
 
+use std::num::{zero, one, cast, sqrt};
+
 use matrix::{Matrix, Mat4};
 use plane::Plane;
-use point::Point3;
-use vector::{Vector, EuclideanVector};
+use point::{Point, Point3};
+use projection::{DepthRange, NegativeOneToOne, ZeroToOne, OneToZero};
+use sphere::Sphere;
+use vector::{Vec3, Vec4, Vector, EuclideanVector};
 
+/// A view frustum. The far plane is `None` for projections where the far
+/// plane is at infinity.
 #[deriving(Clone, Eq)]
 pub struct Frustum<S> {
     left:   Plane<S>,
@@ -27,14 +33,14 @@ pub struct Frustum<S> {
     bottom: Plane<S>,
     top:    Plane<S>,
     near:   Plane<S>,
-    far:    Plane<S>,
+    far:    Option<Plane<S>>,
 }
 
 impl<S: Float> Frustum<S> {
     /// Constructs a frustum
     pub fn new(left:   Plane<S>, right:  Plane<S>,
                bottom: Plane<S>, top:    Plane<S>,
-               near:   Plane<S>, far:    Plane<S>) -> Frustum<S> {
+               near:   Plane<S>, far:    Option<Plane<S>>) -> Frustum<S> {
         Frustum {
             left:   left,
             right:  right,
@@ -45,17 +51,42 @@ impl<S: Float> Frustum<S> {
         }
     }
 
-    /// Extracts frustum planes from a projection matrix
+    /// Extracts frustum planes from an OpenGL style projection matrix, which
+    /// maps depth values to the range `[-1, 1]`.
     pub fn from_mat4(mat: Mat4<S>) -> Frustum<S> {
-        Frustum::new(Plane::from_vec4(mat.r(3).add_v(&mat.r(0)).normalize()),
-                     Plane::from_vec4(mat.r(3).sub_v(&mat.r(0)).normalize()),
-                     Plane::from_vec4(mat.r(3).add_v(&mat.r(1)).normalize()),
-                     Plane::from_vec4(mat.r(3).sub_v(&mat.r(1)).normalize()),
-                     Plane::from_vec4(mat.r(3).add_v(&mat.r(2)).normalize()),
-                     Plane::from_vec4(mat.r(3).sub_v(&mat.r(2)).normalize()))
+        Frustum::from_mat4_depth(mat, NegativeOneToOne)
+    }
+
+    /// Extracts frustum planes from a projection matrix that maps depth
+    /// values to the range given by `depth`.
+    pub fn from_mat4_depth(mat: Mat4<S>, depth: DepthRange) -> Frustum<S> {
+        let (near, far) = match depth {
+            NegativeOneToOne => (mat.r(3).add_v(&mat.r(2)), mat.r(3).sub_v(&mat.r(2))),
+            ZeroToOne        => (mat.r(2),                   mat.r(3).sub_v(&mat.r(2))),
+            OneToZero        => (mat.r(3).sub_v(&mat.r(2)), mat.r(2)),
+        };
+        // the far plane vanishes when it is at infinity
+        let far = if far.x == zero() && far.y == zero() && far.z == zero() {
+            None
+        } else {
+            Some(plane_from_row(far))
+        };
+        Frustum::new(plane_from_row(mat.r(3).add_v(&mat.r(0))),
+                     plane_from_row(mat.r(3).sub_v(&mat.r(0))),
+                     plane_from_row(mat.r(3).add_v(&mat.r(1))),
+                     plane_from_row(mat.r(3).sub_v(&mat.r(1))),
+                     plane_from_row(near),
+                     far)
     }
 }
 
+/// Normalizes a plane extracted from a row combination of a projection
+/// matrix, so that its normal has unit length.
+fn plane_from_row<S: Float>(v: Vec4<S>) -> Plane<S> {
+    let length = Vec3::new(v.x.clone(), v.y.clone(), v.z.clone()).length();
+    Plane::from_vec4(v.div_s(length))
+}
+
 #[deriving(Clone, Eq)]
 pub struct FrustumPoints<S> {
     near_top_left:     Point3<S>,
@@ -67,3 +98,50 @@ pub struct FrustumPoints<S> {
     far_bottom_left:   Point3<S>,
     far_bottom_right:  Point3<S>,
 }
+
+impl<S: Float> FrustumPoints<S> {
+    /// The eight corners of the frustum.
+    pub fn to_vec(&self) -> ~[Point3<S>] {
+        ~[self.near_top_left.clone(), self.near_top_right.clone(),
+          self.near_bottom_left.clone(), self.near_bottom_right.clone(),
+          self.far_top_left.clone(), self.far_top_right.clone(),
+          self.far_bottom_left.clone(), self.far_bottom_right.clone()]
+    }
+
+    /// Transform the corners of the frustum by a homogeneous matrix.
+    pub fn transform(&self, mat: &Mat4<S>) -> FrustumPoints<S> {
+        FrustumPoints {
+            near_top_left:     transform_point(mat, &self.near_top_left),
+            near_top_right:    transform_point(mat, &self.near_top_right),
+            near_bottom_left:  transform_point(mat, &self.near_bottom_left),
+            near_bottom_right: transform_point(mat, &self.near_bottom_right),
+            far_top_left:      transform_point(mat, &self.far_top_left),
+            far_top_right:     transform_point(mat, &self.far_top_right),
+            far_bottom_left:   transform_point(mat, &self.far_bottom_left),
+            far_bottom_right:  transform_point(mat, &self.far_bottom_right),
+        }
+    }
+
+    /// A sphere enclosing the frustum, centered on the average of its
+    /// corners. The size of the sphere does not depend on the orientation of
+    /// the frustum.
+    pub fn bounding_sphere(&self) -> Sphere<S> {
+        let points = self.to_vec();
+        let mut sum = Vec3::zero();
+        for p in points.iter() {
+            sum.add_self_v(&p.sub_p(&Point3::origin()));
+        }
+        let center = Point3::origin().add_v(&sum.div_s(cast(points.len()).unwrap()));
+        let mut radius2 = zero::<S>();
+        for p in points.iter() {
+            let d2 = p.sub_p(&center).length2();
+            if d2 > radius2 { radius2 = d2; }
+        }
+        Sphere { center: center, radius: sqrt(radius2) }
+    }
+}
+
+fn transform_point<S: Float>(mat: &Mat4<S>, p: &Point3<S>) -> Point3<S> {
+    let v = mat.mul_v(&Vec4::new(p.x.clone(), p.y.clone(), p.z.clone(), one()));
+    Point3::new(v.x / v.w, v.y / v.w, v.z / v.w)
+}
@@ -24,9 +24,19 @@
 #[feature(globs)];
 #[feature(macro_rules)];
 
+#[cfg(serialize)]
+extern mod extra;
+
+pub mod approx;
 pub mod array;
+pub mod buffer;
+pub mod dual_quaternion;
+pub mod format;
 pub mod matrix;
 pub mod quaternion;
+pub mod simd;
+pub mod slice;
+pub mod soa;
 pub mod vector;
 
 pub mod angle;
@@ -35,6 +45,7 @@ pub mod point;
 pub mod ray;
 pub mod rotation;
 
+pub mod cascade;
 pub mod projection;
 
 pub mod aabb;
@@ -42,4 +53,8 @@ pub mod cylinder;
 pub mod frustum;
 pub mod intersect;
 pub mod obb;
+pub mod polyhedron;
 pub mod sphere;
+
+#[cfg(serialize)]
+pub mod serialize;
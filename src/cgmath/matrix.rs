@@ -13,25 +13,32 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Column major, square matrix types and traits.
+//! Column major matrix types and traits.
 
+use std::from_str::FromStr;
 use std::num::{Zero, zero, One, one, cast, sqrt};
 
 use angle::{Angle, Rad, sin, cos, sin_cos};
+use approx::{RelativeEq, UlpsEq, default_epsilon};
 use array::{Array, build};
+use format::{ToStrDigits, format_rows, parse_rows};
+use point::{Point2, Point3};
 use quaternion::{Quat, ToQuat};
 use vector::{Vector, EuclideanVector};
 use vector::{Vec2, Vec3, Vec4};
 
 /// A 2 x 2, column major matrix
+#[repr(C)]
 #[deriving(Clone, Eq, Zero)]
 pub struct Mat2<S> { x: Vec2<S>, y: Vec2<S> }
 
 /// A 3 x 3, column major matrix
+#[repr(C)]
 #[deriving(Clone, Eq, Zero)]
 pub struct Mat3<S> { x: Vec3<S>, y: Vec3<S>, z: Vec3<S> }
 
 /// A 4 x 4, column major matrix
+#[repr(C)]
 #[deriving(Clone, Eq, Zero)]
 pub struct Mat4<S> { x: Vec4<S>, y: Vec4<S>, z: Vec4<S>, w: Vec4<S> }
 
@@ -39,6 +46,10 @@ approx_eq!(impl<S> Mat2<S>)
 approx_eq!(impl<S> Mat3<S>)
 approx_eq!(impl<S> Mat4<S>)
 
+format_matrix!(impl<S> Mat2<S>, 2, 2)
+format_matrix!(impl<S> Mat3<S>, 3, 3)
+format_matrix!(impl<S> Mat4<S>, 4, 4)
+
 impl<S: Primitive> Mat2<S> {
     #[inline]
     pub fn new(c0r0: S, c0r1: S,
@@ -70,13 +81,14 @@ impl<S: Primitive> Mat2<S> {
 }
 
 impl<S: Float> Mat2<S> {
+    /// Create a matrix from a counter-clockwise rotation by `theta`.
     #[inline]
     pub fn from_angle(theta: Rad<S>) -> Mat2<S> {
         let cos_theta = cos(theta.clone());
         let sin_theta = sin(theta.clone());
 
-        Mat2::new(cos_theta.clone(),  -sin_theta.clone(),
-                  sin_theta.clone(),  cos_theta.clone())
+        Mat2::new(cos_theta.clone(),  sin_theta.clone(),
+                  -sin_theta.clone(), cos_theta.clone())
     }
 }
 
@@ -113,6 +125,81 @@ impl<S: Primitive> Mat3<S> {
     }
 }
 
+/// Bulk transforms of slices, with the columns of the matrix hoisted out of
+/// the loop.
+impl<S: Primitive> Mat3<S> {
+    /// Multiply every vector in `src` by the matrix, writing the results to
+    /// `dst`, which must be the same length as `src`.
+    pub fn transform_vectors(&self, src: &[Vec3<S>], dst: &mut [Vec3<S>]) {
+        assert_eq!(src.len(), dst.len());
+        let (c0, c1, c2) = (self.x.clone(), self.y.clone(), self.z.clone());
+        for (v, out) in src.iter().zip(dst.mut_iter()) {
+            *out = Vec3::new(c0.x * v.x + c1.x * v.y + c2.x * v.z,
+                             c0.y * v.x + c1.y * v.y + c2.y * v.z,
+                             c0.z * v.x + c1.z * v.y + c2.z * v.z);
+        }
+    }
+
+    /// Multiply every point in `src` by the matrix, as a linear transform
+    /// about the origin, writing the results to `dst`, which must be the same
+    /// length as `src`.
+    pub fn transform_points(&self, src: &[Point3<S>], dst: &mut [Point3<S>]) {
+        assert_eq!(src.len(), dst.len());
+        let (c0, c1, c2) = (self.x.clone(), self.y.clone(), self.z.clone());
+        for (p, out) in src.iter().zip(dst.mut_iter()) {
+            *out = Point3::new(c0.x * p.x + c1.x * p.y + c2.x * p.z,
+                               c0.y * p.x + c1.y * p.y + c2.y * p.z,
+                               c0.z * p.x + c1.z * p.y + c2.z * p.z);
+        }
+    }
+}
+
+/// Two-dimensional affine transforms, using the matrix as a homogeneous
+/// transform in the `xy` plane.
+impl<S: Float> Mat3<S> {
+    /// Create a homogeneous matrix from a two-dimensional translation.
+    pub fn from_translation2(v: &Vec2<S>) -> Mat3<S> {
+        Mat3::new(one(), zero(), zero(),
+                  zero(), one(), zero(),
+                  v.x.clone(), v.y.clone(), one())
+    }
+
+    /// Create a homogeneous matrix from a non-uniform two-dimensional scale.
+    pub fn from_scale2(x: S, y: S) -> Mat3<S> {
+        Mat3::new(x, zero(), zero(),
+                  zero(), y, zero(),
+                  zero(), zero(), one())
+    }
+
+    /// Create a homogeneous matrix from a two-dimensional shear, where `x` is
+    /// the amount `x` is shifted for each unit of `y`, and `y` is the amount
+    /// `y` is shifted for each unit of `x`.
+    pub fn from_shear2(x: S, y: S) -> Mat3<S> {
+        Mat3::new(one(), y, zero(),
+                  x, one(), zero(),
+                  zero(), zero(), one())
+    }
+
+    /// Create a homogeneous matrix from a counter-clockwise rotation in the
+    /// `xy` plane. This is equivalent to a rotation around the `z` axis.
+    #[inline]
+    pub fn from_angle<A: Angle<S>>(theta: A) -> Mat3<S> {
+        Mat3::from_angle_z(theta)
+    }
+
+    /// Transform a point by the matrix, applying the translation.
+    pub fn transform_point2(&self, point: &Point2<S>) -> Point2<S> {
+        let v = self.mul_v(&Vec3::new(point.x.clone(), point.y.clone(), one()));
+        Point2::new(v.x.clone(), v.y.clone())
+    }
+
+    /// Transform a vector by the matrix, ignoring the translation.
+    pub fn transform_vector2(&self, vec: &Vec2<S>) -> Vec2<S> {
+        let v = self.mul_v(&Vec3::new(vec.x.clone(), vec.y.clone(), zero()));
+        Vec2::new(v.x.clone(), v.y.clone())
+    }
+}
+
 impl<S: Float> Mat3<S> {
     pub fn look_at(dir: &Vec3<S>, up: &Vec3<S>) -> Mat3<S> {
         let dir  = dir.normalize();
@@ -184,6 +271,37 @@ impl<S: Float> Mat3<S> {
                   _1subc * axis.y * axis.z - s * axis.x,
                   _1subc * axis.z * axis.z + c)
     }
+
+    /// Decompose the matrix into an orthogonal factor and a symmetric stretch,
+    /// such that `self == rotation.mul_m(&stretch)`.
+    ///
+    /// The orthogonal factor is found by repeatedly averaging the matrix with
+    /// its inverse transpose, which converges quadratically to the closest
+    /// orthonormal matrix (see Higham, _Computing the Polar Decomposition_). If
+    /// the determinant of the matrix is negative the orthogonal factor will
+    /// contain a reflection.
+    ///
+    /// # Return value
+    ///
+    /// `Some((rotation, stretch))`, or `None` if the matrix is singular.
+    pub fn polar_decomposition(&self) -> Option<(Mat3<S>, Mat3<S>)> {
+        let half: S = cast(0.5).unwrap();
+        let mut rot = self.clone();
+
+        for _ in range(0u, 32u) {
+            let inv_t = match rot.invert() {
+                Some(inv) => inv.transpose(),
+                None => return None,
+            };
+            let next = rot.add_m(&inv_t).mul_s(half.clone());
+            let converged = next.approx_eq(&rot);
+            rot = next;
+            if converged { break; }
+        }
+
+        let stretch = rot.transpose().mul_m(self);
+        Some((rot, stretch))
+    }
 }
 
 impl<S: Primitive> Mat4<S> {
@@ -222,6 +340,52 @@ impl<S: Primitive> Mat4<S> {
     }
 }
 
+/// Bulk transforms of slices, with the columns of the matrix hoisted out of
+/// the loop.
+impl<S: Primitive> Mat4<S> {
+    /// Transform every point in `src` by the matrix, as an affine transform
+    /// with a `w` component of one, writing the results to `dst`, which must
+    /// be the same length as `src`. The bottom row of the matrix is ignored.
+    pub fn transform_points(&self, src: &[Point3<S>], dst: &mut [Point3<S>]) {
+        assert_eq!(src.len(), dst.len());
+        let (c0, c1, c2, c3) = (self.x.clone(), self.y.clone(), self.z.clone(), self.w.clone());
+        for (p, out) in src.iter().zip(dst.mut_iter()) {
+            *out = Point3::new(c0.x * p.x + c1.x * p.y + c2.x * p.z + c3.x,
+                               c0.y * p.x + c1.y * p.y + c2.y * p.z + c3.y,
+                               c0.z * p.x + c1.z * p.y + c2.z * p.z + c3.z);
+        }
+    }
+
+    /// Transform every vector in `src` by the matrix, with a `w` component of
+    /// zero, so that the translation does not apply, writing the results to
+    /// `dst`, which must be the same length as `src`.
+    pub fn transform_vectors(&self, src: &[Vec3<S>], dst: &mut [Vec3<S>]) {
+        assert_eq!(src.len(), dst.len());
+        let (c0, c1, c2) = (self.x.clone(), self.y.clone(), self.z.clone());
+        for (v, out) in src.iter().zip(dst.mut_iter()) {
+            *out = Vec3::new(c0.x * v.x + c1.x * v.y + c2.x * v.z,
+                             c0.y * v.x + c1.y * v.y + c2.y * v.z,
+                             c0.z * v.x + c1.z * v.y + c2.z * v.z);
+        }
+    }
+
+    /// Transform every point in `src` by the matrix, with a `w` component of
+    /// one, and divide the result by its `w` component, writing the results
+    /// to `dst`, which must be the same length as `src`. This is the usual way
+    /// of applying a projection matrix. Points that end up with a `w` of zero
+    /// are not finite.
+    pub fn project_points(&self, src: &[Point3<S>], dst: &mut [Point3<S>]) {
+        assert_eq!(src.len(), dst.len());
+        let (c0, c1, c2, c3) = (self.x.clone(), self.y.clone(), self.z.clone(), self.w.clone());
+        for (p, out) in src.iter().zip(dst.mut_iter()) {
+            let w = c0.w * p.x + c1.w * p.y + c2.w * p.z + c3.w;
+            *out = Point3::new((c0.x * p.x + c1.x * p.y + c2.x * p.z + c3.x) / w,
+                               (c0.y * p.x + c1.y * p.y + c2.y * p.z + c3.y) / w,
+                               (c0.z * p.x + c1.z * p.y + c2.z * p.z + c3.z) / w);
+        }
+    }
+}
+
 impl<S: Float> One for Mat2<S> { #[inline] fn one() -> Mat2<S> { Mat2::identity() } }
 impl<S: Float> One for Mat3<S> { #[inline] fn one() -> Mat3<S> { Mat3::identity() } }
 impl<S: Float> One for Mat4<S> { #[inline] fn one() -> Mat4<S> { Mat4::identity() } }
@@ -586,6 +750,230 @@ for Mat4<S>
     }
 }
 
+/// A 2 x 3 (two column, three row), column major matrix
+#[repr(C)]
+#[deriving(Clone, Eq, Zero)]
+pub struct Mat2x3<S> { x: Vec3<S>, y: Vec3<S> }
+
+/// A 2 x 4 (two column, four row), column major matrix
+#[repr(C)]
+#[deriving(Clone, Eq, Zero)]
+pub struct Mat2x4<S> { x: Vec4<S>, y: Vec4<S> }
+
+/// A 3 x 2 (three column, two row), column major matrix. This is useful for
+/// representing two-dimensional affine transforms.
+#[repr(C)]
+#[deriving(Clone, Eq, Zero)]
+pub struct Mat3x2<S> { x: Vec2<S>, y: Vec2<S>, z: Vec2<S> }
+
+/// A 3 x 4 (three column, four row), column major matrix
+#[repr(C)]
+#[deriving(Clone, Eq, Zero)]
+pub struct Mat3x4<S> { x: Vec4<S>, y: Vec4<S>, z: Vec4<S> }
+
+/// A 4 x 2 (four column, two row), column major matrix
+#[repr(C)]
+#[deriving(Clone, Eq, Zero)]
+pub struct Mat4x2<S> { x: Vec2<S>, y: Vec2<S>, z: Vec2<S>, w: Vec2<S> }
+
+/// A 4 x 3 (four column, three row), column major matrix. This is useful for
+/// representing three-dimensional affine transforms.
+#[repr(C)]
+#[deriving(Clone, Eq, Zero)]
+pub struct Mat4x3<S> { x: Vec3<S>, y: Vec3<S>, z: Vec3<S>, w: Vec3<S> }
+
+array!(impl<S> Mat2x3<S> -> [Vec3<S>, ..2] _2)
+array!(impl<S> Mat2x4<S> -> [Vec4<S>, ..2] _2)
+array!(impl<S> Mat3x2<S> -> [Vec2<S>, ..3] _3)
+array!(impl<S> Mat3x4<S> -> [Vec4<S>, ..3] _3)
+array!(impl<S> Mat4x2<S> -> [Vec2<S>, ..4] _4)
+array!(impl<S> Mat4x3<S> -> [Vec3<S>, ..4] _4)
+
+approx_eq!(impl<S> Mat2x3<S>)
+approx_eq!(impl<S> Mat2x4<S>)
+approx_eq!(impl<S> Mat3x2<S>)
+approx_eq!(impl<S> Mat3x4<S>)
+approx_eq!(impl<S> Mat4x2<S>)
+approx_eq!(impl<S> Mat4x3<S>)
+
+format_matrix!(impl<S> Mat2x3<S>, 2, 3)
+format_matrix!(impl<S> Mat2x4<S>, 2, 4)
+format_matrix!(impl<S> Mat3x2<S>, 3, 2)
+format_matrix!(impl<S> Mat3x4<S>, 3, 4)
+format_matrix!(impl<S> Mat4x2<S>, 4, 2)
+format_matrix!(impl<S> Mat4x3<S>, 4, 3)
+
+impl<S: Primitive> Mat2x3<S> {
+    #[inline]
+    pub fn new(c0r0: S, c0r1: S, c0r2: S,
+               c1r0: S, c1r1: S, c1r2: S) -> Mat2x3<S> {
+        Mat2x3::from_cols(Vec3::new(c0r0, c0r1, c0r2),
+                          Vec3::new(c1r0, c1r1, c1r2))
+    }
+}
+
+impl<S: Primitive> Mat2x4<S> {
+    #[inline]
+    pub fn new(c0r0: S, c0r1: S, c0r2: S, c0r3: S,
+               c1r0: S, c1r1: S, c1r2: S, c1r3: S) -> Mat2x4<S> {
+        Mat2x4::from_cols(Vec4::new(c0r0, c0r1, c0r2, c0r3),
+                          Vec4::new(c1r0, c1r1, c1r2, c1r3))
+    }
+}
+
+impl<S: Primitive> Mat3x2<S> {
+    #[inline]
+    pub fn new(c0r0: S, c0r1: S,
+               c1r0: S, c1r1: S,
+               c2r0: S, c2r1: S) -> Mat3x2<S> {
+        Mat3x2::from_cols(Vec2::new(c0r0, c0r1),
+                          Vec2::new(c1r0, c1r1),
+                          Vec2::new(c2r0, c2r1))
+    }
+}
+
+impl<S: Primitive> Mat3x4<S> {
+    #[inline]
+    pub fn new(c0r0: S, c0r1: S, c0r2: S, c0r3: S,
+               c1r0: S, c1r1: S, c1r2: S, c1r3: S,
+               c2r0: S, c2r1: S, c2r2: S, c2r3: S) -> Mat3x4<S> {
+        Mat3x4::from_cols(Vec4::new(c0r0, c0r1, c0r2, c0r3),
+                          Vec4::new(c1r0, c1r1, c1r2, c1r3),
+                          Vec4::new(c2r0, c2r1, c2r2, c2r3))
+    }
+}
+
+impl<S: Primitive> Mat4x2<S> {
+    #[inline]
+    pub fn new(c0r0: S, c0r1: S,
+               c1r0: S, c1r1: S,
+               c2r0: S, c2r1: S,
+               c3r0: S, c3r1: S) -> Mat4x2<S> {
+        Mat4x2::from_cols(Vec2::new(c0r0, c0r1),
+                          Vec2::new(c1r0, c1r1),
+                          Vec2::new(c2r0, c2r1),
+                          Vec2::new(c3r0, c3r1))
+    }
+}
+
+impl<S: Primitive> Mat4x3<S> {
+    #[inline]
+    pub fn new(c0r0: S, c0r1: S, c0r2: S,
+               c1r0: S, c1r1: S, c1r2: S,
+               c2r0: S, c2r1: S, c2r2: S,
+               c3r0: S, c3r1: S, c3r2: S) -> Mat4x3<S> {
+        Mat4x3::from_cols(Vec3::new(c0r0, c0r1, c0r2),
+                          Vec3::new(c1r0, c1r1, c1r2),
+                          Vec3::new(c2r0, c2r1, c2r2),
+                          Vec3::new(c3r0, c3r1, c3r2))
+    }
+}
+
+// Utility macro for generating the operations shared by the non-square
+// matrices. `$V` is the type of the columns, `$R` is the type of the rows, and
+// `$T` is the type of the transposed matrix.
+macro_rules! mat_nonsquare(
+    (impl $Self:ident <$S:ident> { $($field:ident),+ }: $V:ident, $R:ident, $T:ident) => (
+        impl<$S: Primitive> $Self<$S> {
+            #[inline]
+            pub fn from_cols($($field: $V<$S>),+) -> $Self<$S> {
+                $Self { $($field: $field),+ }
+            }
+
+            #[inline]
+            pub fn zero() -> $Self<$S> { zero() }
+        }
+
+        impl<$S: Float> $Self<$S> {
+            #[inline]
+            pub fn c<'a>(&'a self, c: uint) -> &'a $V<$S> { self.i(c) }
+
+            #[inline]
+            pub fn r(&self, r: uint) -> $R<$S> { build(|i| self.i(i).i(r).clone()) }
+
+            #[inline]
+            pub fn cr<'a>(&'a self, c: uint, r: uint) -> &'a $S { self.i(c).i(r) }
+
+            #[inline] pub fn mul_s(&self, s: $S) -> $Self<$S> { build(|i| self.i(i).mul_s(s.clone())) }
+            #[inline] pub fn div_s(&self, s: $S) -> $Self<$S> { build(|i| self.i(i).div_s(s.clone())) }
+
+            #[inline] pub fn add_m(&self, other: &$Self<$S>) -> $Self<$S> { build(|i| self.i(i).add_v(other.i(i))) }
+            #[inline] pub fn sub_m(&self, other: &$Self<$S>) -> $Self<$S> { build(|i| self.i(i).sub_v(other.i(i))) }
+
+            /// Multiply the matrix by a vector with one component per column.
+            #[inline]
+            pub fn mul_v(&self, v: &$R<$S>) -> $V<$S> {
+                let mut result: $V<$S> = zero();
+                $(result.add_self_v(&self.$field.mul_s(v.$field.clone()));)+
+                result
+            }
+
+            #[inline]
+            pub fn transpose(&self) -> $T<$S> { build(|i| self.r(i)) }
+        }
+
+        impl<$S: Float> Neg<$Self<$S>> for $Self<$S> {
+            #[inline] fn neg(&self) -> $Self<$S> { build(|i| self.i(i).neg()) }
+        }
+    )
+)
+
+mat_nonsquare!(impl Mat2x3<S> { x, y }: Vec3, Vec2, Mat3x2)
+mat_nonsquare!(impl Mat2x4<S> { x, y }: Vec4, Vec2, Mat4x2)
+mat_nonsquare!(impl Mat3x2<S> { x, y, z }: Vec2, Vec3, Mat2x3)
+mat_nonsquare!(impl Mat3x4<S> { x, y, z }: Vec4, Vec3, Mat4x3)
+mat_nonsquare!(impl Mat4x2<S> { x, y, z, w }: Vec2, Vec4, Mat2x4)
+mat_nonsquare!(impl Mat4x3<S> { x, y, z, w }: Vec3, Vec4, Mat3x4)
+
+/// Multiplication between matrices of compatible dimensions. The number of
+/// columns of the left hand side must equal the number of rows of the right
+/// hand side, and the result has the rows of the former and the columns of
+/// the latter.
+pub trait MatrixProduct<RHS, Result> {
+    fn mul_mat(&self, other: &RHS) -> Result;
+}
+
+// Each column of the product is the left hand side multiplied by the
+// corresponding column of the right hand side.
+macro_rules! mat_product(
+    ($LHS:ident * $RHS:ident -> $Result:ident { $($c:expr),+ }) => (
+        impl<S: Float> MatrixProduct<$RHS<S>, $Result<S>> for $LHS<S> {
+            #[inline]
+            fn mul_mat(&self, other: &$RHS<S>) -> $Result<S> {
+                $Result::from_cols($(self.mul_v(other.i($c))),+)
+            }
+        }
+    )
+)
+
+mat_product!(Mat2 * Mat2 -> Mat2 { 0, 1 })
+mat_product!(Mat2 * Mat3x2 -> Mat3x2 { 0, 1, 2 })
+mat_product!(Mat2 * Mat4x2 -> Mat4x2 { 0, 1, 2, 3 })
+mat_product!(Mat2x3 * Mat2 -> Mat2x3 { 0, 1 })
+mat_product!(Mat2x3 * Mat3x2 -> Mat3 { 0, 1, 2 })
+mat_product!(Mat2x3 * Mat4x2 -> Mat4x3 { 0, 1, 2, 3 })
+mat_product!(Mat2x4 * Mat2 -> Mat2x4 { 0, 1 })
+mat_product!(Mat2x4 * Mat3x2 -> Mat3x4 { 0, 1, 2 })
+mat_product!(Mat2x4 * Mat4x2 -> Mat4 { 0, 1, 2, 3 })
+mat_product!(Mat3x2 * Mat2x3 -> Mat2 { 0, 1 })
+mat_product!(Mat3x2 * Mat3 -> Mat3x2 { 0, 1, 2 })
+mat_product!(Mat3x2 * Mat4x3 -> Mat4x2 { 0, 1, 2, 3 })
+mat_product!(Mat3 * Mat2x3 -> Mat2x3 { 0, 1 })
+mat_product!(Mat3 * Mat3 -> Mat3 { 0, 1, 2 })
+mat_product!(Mat3 * Mat4x3 -> Mat4x3 { 0, 1, 2, 3 })
+mat_product!(Mat3x4 * Mat2x3 -> Mat2x4 { 0, 1 })
+mat_product!(Mat3x4 * Mat3 -> Mat3x4 { 0, 1, 2 })
+mat_product!(Mat3x4 * Mat4x3 -> Mat4 { 0, 1, 2, 3 })
+mat_product!(Mat4x2 * Mat2x4 -> Mat2 { 0, 1 })
+mat_product!(Mat4x2 * Mat3x4 -> Mat3x2 { 0, 1, 2 })
+mat_product!(Mat4x2 * Mat4 -> Mat4x2 { 0, 1, 2, 3 })
+mat_product!(Mat4x3 * Mat2x4 -> Mat2x3 { 0, 1 })
+mat_product!(Mat4x3 * Mat3x4 -> Mat3 { 0, 1, 2 })
+mat_product!(Mat4x3 * Mat4 -> Mat4x3 { 0, 1, 2, 3 })
+mat_product!(Mat4 * Mat2x4 -> Mat2x4 { 0, 1 })
+mat_product!(Mat4 * Mat3x4 -> Mat3x4 { 0, 1, 2 })
+mat_product!(Mat4 * Mat4 -> Mat4 { 0, 1, 2, 3 })
+
 // Conversion traits
 pub trait ToMat2<S: Primitive> { fn to_mat2(&self) -> Mat2<S>; }
 pub trait ToMat3<S: Primitive> { fn to_mat3(&self) -> Mat3<S>; }
@@ -623,10 +1011,35 @@ impl<S: Float> ToMat4<S> for Mat3<S> {
     }
 }
 
+impl<S: Float> ToMat3<S> for Mat3x2<S> {
+    /// Extend a two-dimensional affine transform to a homogeneous 3 x 3
+    /// matrix by appending the row `[0, 0, 1]`.
+    fn to_mat3(&self) -> Mat3<S> {
+        Mat3::new(self.cr(0, 0).clone(), self.cr(0, 1).clone(), zero(),
+                  self.cr(1, 0).clone(), self.cr(1, 1).clone(), zero(),
+                  self.cr(2, 0).clone(), self.cr(2, 1).clone(), one())
+    }
+}
+
+impl<S: Float> ToMat4<S> for Mat4x3<S> {
+    /// Extend a three-dimensional affine transform to a homogeneous 4 x 4
+    /// matrix by appending the row `[0, 0, 0, 1]`.
+    fn to_mat4(&self) -> Mat4<S> {
+        Mat4::new(self.cr(0, 0).clone(), self.cr(0, 1).clone(), self.cr(0, 2).clone(), zero(),
+                  self.cr(1, 0).clone(), self.cr(1, 1).clone(), self.cr(1, 2).clone(), zero(),
+                  self.cr(2, 0).clone(), self.cr(2, 1).clone(), self.cr(2, 2).clone(), zero(),
+                  self.cr(3, 0).clone(), self.cr(3, 1).clone(), self.cr(3, 2).clone(), one())
+    }
+}
+
 impl<S:Float> ToQuat<S> for Mat3<S> {
     /// Convert the matrix to a quaternion
     fn to_quat(&self) -> Quat<S> {
         // http://www.cs.ucr.edu/~vbz/resources/Quatut.pdf
+        //
+        // The largest of the quaternion's components is computed first and
+        // used to derive the others, which keeps the conversion stable for
+        // rotations approaching a half turn.
         let trace = self.trace();
         let half: S = cast(0.5).unwrap();
         match () {
@@ -640,30 +1053,30 @@ impl<S:Float> ToQuat<S> for Mat3<S> {
                 Quat::new(w, x, y, z)
             }
             () if (*self.cr(0, 0) > *self.cr(1, 1)) && (*self.cr(0, 0) > *self.cr(2, 2)) => {
-                let s = sqrt(half + (*self.cr(0, 0) - *self.cr(1, 1) - *self.cr(2, 2)));
-                let w = half * s;
+                let s = sqrt(one::<S>() + *self.cr(0, 0) - *self.cr(1, 1) - *self.cr(2, 2));
+                let x = half * s;
                 let s = half / s;
-                let x = (*self.cr(0, 1) - *self.cr(1, 0)) * s;
-                let y = (*self.cr(2, 0) - *self.cr(0, 2)) * s;
-                let z = (*self.cr(1, 2) - *self.cr(2, 1)) * s;
+                let w = (*self.cr(1, 2) - *self.cr(2, 1)) * s;
+                let y = (*self.cr(1, 0) + *self.cr(0, 1)) * s;
+                let z = (*self.cr(2, 0) + *self.cr(0, 2)) * s;
                 Quat::new(w, x, y, z)
             }
             () if *self.cr(1, 1) > *self.cr(2, 2) => {
-                let s = sqrt(half + (*self.cr(1, 1) - *self.cr(0, 0) - *self.cr(2, 2)));
-                let w = half * s;
+                let s = sqrt(one::<S>() + *self.cr(1, 1) - *self.cr(0, 0) - *self.cr(2, 2));
+                let y = half * s;
                 let s = half / s;
-                let x = (*self.cr(0, 1) - *self.cr(1, 0)) * s;
-                let y = (*self.cr(1, 2) - *self.cr(2, 1)) * s;
-                let z = (*self.cr(2, 0) - *self.cr(0, 2)) * s;
+                let w = (*self.cr(2, 0) - *self.cr(0, 2)) * s;
+                let x = (*self.cr(1, 0) + *self.cr(0, 1)) * s;
+                let z = (*self.cr(2, 1) + *self.cr(1, 2)) * s;
                 Quat::new(w, x, y, z)
             }
             () => {
-                let s = sqrt(half + (*self.cr(2, 2) - *self.cr(0, 0) - *self.cr(1, 1)));
-                let w = half * s;
+                let s = sqrt(one::<S>() + *self.cr(2, 2) - *self.cr(0, 0) - *self.cr(1, 1));
+                let z = half * s;
                 let s = half / s;
-                let x = (*self.cr(2, 0) - *self.cr(0, 2)) * s;
-                let y = (*self.cr(1, 2) - *self.cr(2, 1)) * s;
-                let z = (*self.cr(0, 1) - *self.cr(1, 0)) * s;
+                let w = (*self.cr(0, 1) - *self.cr(1, 0)) * s;
+                let x = (*self.cr(2, 0) + *self.cr(0, 2)) * s;
+                let y = (*self.cr(2, 1) + *self.cr(1, 2)) * s;
                 Quat::new(w, x, y, z)
             }
         }
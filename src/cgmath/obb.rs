@@ -15,6 +15,7 @@
 
 //! Oriented bounding boxes
 
+use approx::{RelativeEq, UlpsEq, default_epsilon};
 use point::{Point2, Point3};
 use vector::{Vec2, Vec3};
 
@@ -31,3 +32,6 @@ pub struct Obb3<S> {
     axis: Vec3<S>,
     extents: Vec3<S>,
 }
+
+approx_eq_fields!(impl<S> Obb2<S> { center, axis, extents })
+approx_eq_fields!(impl<S> Obb3<S> { center, axis, extents })
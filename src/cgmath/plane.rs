@@ -14,15 +14,16 @@
 // limitations under the License.
 
 use std::cast::transmute;
+use std::from_str::FromStr;
 
+use approx::{RelativeEq, UlpsEq, default_epsilon};
+use format::{ToStrDigits, format_list, parse_list};
 use intersect::Intersect;
 use point::{Point, Point3};
 use ray::Ray3;
 use vector::{Vec3, Vec4};
 use vector::{Vector, EuclideanVector};
 
-use std::fmt;
-
 /// A 3-dimendional plane formed from the equation: `a*x + b*y + c*z - d = 0`.
 ///
 /// # Fields
@@ -103,32 +104,27 @@ impl<S: Float> Intersect<Option<Point3<S>>> for (Plane<S>, Plane<S>, Plane<S>) {
     }
 }
 
-impl<S: Float> ApproxEq<S> for Plane<S> {
-    #[inline]
-    fn approx_epsilon() -> S {
-        // TODO: fix this after static methods are fixed in rustc
-        fail!(~"Doesn't work!");
-    }
+approx_eq_fields!(impl<S> Plane<S> { n, d })
 
-    #[inline]
-    fn approx_eq(&self, other: &Plane<S>) -> bool {
-        self.n.approx_eq(&other.n) &&
-        self.d.approx_eq(&other.d)
+impl<S: ToStr> ToStr for Plane<S> {
+    /// Writes the plane as `[a, b, c, d]`, the components of its normal
+    /// followed by its distance value.
+    fn to_str(&self) -> ~str {
+        format_list([self.n.x.to_str(), self.n.y.to_str(), self.n.z.to_str(), self.d.to_str()])
     }
+}
 
-    #[inline]
-    fn approx_eq_eps(&self, other: &Plane<S>, approx_epsilon: &S) -> bool {
-        self.n.approx_eq_eps(&other.n, approx_epsilon) &&
-        self.d.approx_eq_eps(&other.d, approx_epsilon)
+impl<S: ToStrDigits> ToStrDigits for Plane<S> {
+    fn to_str_digits(&self, digits: uint) -> ~str {
+        format_list([self.n.x.to_str_digits(digits), self.n.y.to_str_digits(digits),
+                     self.n.z.to_str_digits(digits), self.d.to_str_digits(digits)])
     }
 }
 
-impl<S: Clone + fmt::Float> ToStr for Plane<S> {
-    fn to_str(&self) -> ~str {
-        format!("{:f}x + {:f}y + {:f}z - {:f} = 0",
-                self.n.x,
-                self.n.y,
-                self.n.z,
-                self.d)
+impl<S: Clone + FromStr> FromStr for Plane<S> {
+    fn from_str(s: &str) -> Option<Plane<S>> {
+        parse_list::<S>(s, 4).map(|v| {
+            Plane { n: Vec3 { x: v[0].clone(), y: v[1].clone(), z: v[2].clone() }, d: v[3].clone() }
+        })
     }
 }
@@ -17,22 +17,30 @@
 //! disinguishes them from vectors, which have a length and direction, but do
 //! not have a fixed position.
 
+use std::from_str::FromStr;
 use std::num::zero;
 
+use approx::{RelativeEq, UlpsEq, default_epsilon};
 use array::*;
+use format::{ToStrDigits, format_list, parse_list};
 use vector::*;
 
 /// A point in 2-dimensional space.
+#[repr(C)]
 #[deriving(Eq, Zero, Clone)]
 struct Point2<S> { x: S, y: S }
 
 /// A point in 2-dimensional space.
+#[repr(C)]
 #[deriving(Eq, Zero, Clone)]
 struct Point3<S> { x: S, y: S, z: S }
 
 approx_eq!(impl<S> Point2<S>)
 approx_eq!(impl<S> Point3<S>)
 
+format_array!(impl<S> Point2<S>, 2)
+format_array!(impl<S> Point3<S>, 3)
+
 impl<S: Num> Point2<S> {
     #[inline]
     pub fn new(x: S, y: S) -> Point2<S> {
@@ -53,6 +61,34 @@ impl<S: Num> Point3<S> {
     pub fn origin() -> Point3<S> { zero() }
 }
 
+impl<S: Primitive> Point2<S> {
+    /// The point at the displacement `v` from the origin.
+    #[inline]
+    pub fn from_vec(v: &Vec2<S>) -> Point2<S> {
+        Point2::new(v.x.clone(), v.y.clone())
+    }
+
+    /// The displacement of the point from the origin.
+    #[inline]
+    pub fn to_vec(&self) -> Vec2<S> {
+        Vec2::new(self.x.clone(), self.y.clone())
+    }
+}
+
+impl<S: Primitive> Point3<S> {
+    /// The point at the displacement `v` from the origin.
+    #[inline]
+    pub fn from_vec(v: &Vec3<S>) -> Point3<S> {
+        Point3::new(v.x.clone(), v.y.clone(), v.z.clone())
+    }
+
+    /// The displacement of the point from the origin.
+    #[inline]
+    pub fn to_vec(&self) -> Vec3<S> {
+        Vec3::new(self.x.clone(), self.y.clone(), self.z.clone())
+    }
+}
+
 /// Specifies the numeric operations for point types.
 pub trait Point
 <
@@ -87,15 +123,3 @@ array!(impl<S> Point3<S> -> [S, ..3] _3)
 
 impl<S: Primitive> Point<S, Vec2<S>, [S, ..2]> for Point2<S> {}
 impl<S: Primitive> Point<S, Vec3<S>, [S, ..3]> for Point3<S> {}
-
-impl<S> ToStr for Point2<S> {
-    fn to_str(&self) -> ~str {
-        fmt!("[%?, %?]", self.x, self.y)
-    }
-}
-
-impl<S> ToStr for Point3<S> {
-    fn to_str(&self) -> ~str {
-        fmt!("[%?, %?, %?]", self.x, self.y, self.z)
-    }
-}
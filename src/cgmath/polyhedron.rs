@@ -0,0 +1,175 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Convex polyhedra defined by a set of bounding planes
+
+use std::num::{zero, one, cast};
+
+use aabb::Aabb3;
+use frustum::Frustum;
+use plane::Plane;
+use point::Point3;
+use sphere::Sphere;
+use vector::Vector;
+
+/// The relationship between a bounding volume and a convex polyhedron.
+#[deriving(Clone, Eq)]
+pub enum Relation {
+    /// The volume is entirely inside the polyhedron.
+    Inside,
+    /// The volume is entirely outside the polyhedron.
+    Outside,
+    /// The volume may cross the boundary of the polyhedron.
+    Intersecting,
+}
+
+/// A convex polyhedron, formed from the intersection of the half-spaces in
+/// front of a set of planes. A point `p` is inside the half-space of a plane
+/// when `n·p + d >= 0`, which is the same convention used by the planes of a
+/// `Frustum`. The normals of the planes are expected to be unit vectors.
+///
+/// This is useful for portal culling, light volumes and clipping regions,
+/// which do not have the fixed six sides of a view frustum.
+#[deriving(Clone, Eq)]
+pub struct ConvexPolyhedron<S> {
+    planes: ~[Plane<S>],
+}
+
+impl<S: Float> ConvexPolyhedron<S> {
+    /// Construct a polyhedron from the planes bounding it.
+    #[inline]
+    pub fn new(planes: ~[Plane<S>]) -> ConvexPolyhedron<S> {
+        ConvexPolyhedron { planes: planes }
+    }
+
+    /// Construct a polyhedron from the planes of a view frustum. The result
+    /// has five planes if the far plane of the frustum is at infinity, and six
+    /// otherwise.
+    pub fn from_frustum(frustum: &Frustum<S>) -> ConvexPolyhedron<S> {
+        let mut planes = ~[frustum.left.clone(), frustum.right.clone(),
+                           frustum.bottom.clone(), frustum.top.clone(),
+                           frustum.near.clone()];
+        for far in frustum.far.iter() {
+            planes.push(far.clone());
+        }
+        ConvexPolyhedron::new(planes)
+    }
+
+    /// Construct a polyhedron from the six faces of an axis-aligned bounding
+    /// box, where `size` is the full extent of the box along each axis.
+    pub fn from_aabb3(aabb: &Aabb3<S>) -> ConvexPolyhedron<S> {
+        let half = aabb.size.div_s(cast(2).unwrap());
+        let c = &aabb.center;
+        let (one, zero): (S, S) = (one(), zero());
+        ConvexPolyhedron::new(~[
+            Plane::from_abcd( one.clone(), zero.clone(), zero.clone(), half.x - c.x),
+            Plane::from_abcd(-one.clone(), zero.clone(), zero.clone(), half.x + c.x),
+            Plane::from_abcd(zero.clone(),  one.clone(), zero.clone(), half.y - c.y),
+            Plane::from_abcd(zero.clone(), -one.clone(), zero.clone(), half.y + c.y),
+            Plane::from_abcd(zero.clone(), zero.clone(),  one.clone(), half.z - c.z),
+            Plane::from_abcd(zero.clone(), zero.clone(), -one.clone(), half.z + c.z),
+        ])
+    }
+
+    /// Construct the convex hull of a set of points.
+    ///
+    /// Every plane passing through three of the points, with all of the
+    /// other points on or in front of it, is a face of the hull. This takes
+    /// `O(n⁴)` time, so it is only suitable for small sets of points, such as
+    /// the corners of a bounding volume.
+    ///
+    /// # Return value
+    ///
+    /// The hull, or `None` if all of the points lie in a single plane.
+    pub fn from_points(points: &[Point3<S>]) -> Option<ConvexPolyhedron<S>> {
+        let mut planes: ~[Plane<S>] = ~[];
+        let n = points.len();
+        for i in range(0, n) {
+            for j in range(i + 1, n) {
+                for k in range(j + 1, n) {
+                    let plane = match Plane::from_points(points[i].clone(),
+                                                         points[j].clone(),
+                                                         points[k].clone()) {
+                        Some(plane) => plane,
+                        None => loop,
+                    };
+                    let front = points.iter().all(|p| !(distance(&plane, p) < zero()) ||
+                                                      distance(&plane, p).approx_eq(&zero()));
+                    let back = points.iter().all(|p| !(distance(&plane, p) > zero()) ||
+                                                     distance(&plane, p).approx_eq(&zero()));
+                    // when neither side is empty, the plane cuts through the
+                    // hull, and when both are, all of the points are coplanar
+                    let face = match (front, back) {
+                        (true, false) => plane,
+                        (false, true) => Plane::new(plane.n.neg(), -plane.d),
+                        _ => loop,
+                    };
+                    if !planes.iter().any(|p| p.approx_eq(&face)) {
+                        planes.push(face);
+                    }
+                }
+            }
+        }
+        if planes.is_empty() { None } else { Some(ConvexPolyhedron::new(planes)) }
+    }
+
+    /// Check whether a point is inside the polyhedron, or on its boundary.
+    pub fn contains_point(&self, point: &Point3<S>) -> bool {
+        self.planes.iter().all(|plane| !(distance(plane, point) < zero()))
+    }
+
+    /// Classify a sphere as inside, outside, or intersecting the polyhedron.
+    pub fn classify_sphere(&self, sphere: &Sphere<S>) -> Relation {
+        self.classify(&sphere.center, |_| sphere.radius.clone())
+    }
+
+    /// Classify an axis-aligned bounding box as inside, outside, or
+    /// intersecting the polyhedron, where `size` is the full extent of the box
+    /// along each axis.
+    ///
+    /// The test is conservative: a box near a corner or an edge of the
+    /// polyhedron may be classified as intersecting, even if it is outside.
+    pub fn classify_aabb3(&self, aabb: &Aabb3<S>) -> Relation {
+        let half = aabb.size.div_s(cast(2).unwrap());
+        // the distance from the center of the box to its furthest corner
+        // along the normal of the plane
+        self.classify(&aabb.center, |plane| {
+            plane.n.x.abs() * half.x + plane.n.y.abs() * half.y + plane.n.z.abs() * half.z
+        })
+    }
+
+    /// Classify a volume, given its center and a function returning its
+    /// extent along the normal of a plane.
+    fn classify(&self, center: &Point3<S>, extent: &fn(&Plane<S>) -> S) -> Relation {
+        let mut result = Inside;
+        for plane in self.planes.iter() {
+            let dist = distance(plane, center);
+            let r = extent(plane);
+            if dist < -r {
+                return Outside;
+            } else if dist < r {
+                result = Intersecting;
+            }
+        }
+        result
+    }
+}
+
+/// The signed distance from a plane to a point, which is positive in front of
+/// the plane.
+#[inline]
+fn distance<S: Float>(plane: &Plane<S>, point: &Point3<S>) -> S {
+    plane.n.dot(&point.to_vec()) + plane.d
+}
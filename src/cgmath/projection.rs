@@ -16,9 +16,13 @@
 use std::num::{zero, one, cast};
 
 use angle::{Angle, tan, cot};
-use frustum::Frustum;
-use matrix::{Mat4, ToMat4};
+use array::Array;
+use frustum::{Frustum, FrustumPoints};
+use matrix::{Matrix, Mat4, ToMat4};
 use plane::Plane;
+use point::{Point, Point3};
+use ray::Ray3;
+use vector::{Vec3, Vec4, Vector, EuclideanVector};
 
 /// Create a perspective projection matrix.
 ///
@@ -63,6 +67,284 @@ pub fn ortho<S: Float>(left: S, right: S, bottom: S, top: S, near: S, far: S) ->
     }.to_mat4()
 }
 
+/// Create a perspective projection matrix that maps depth values to the
+/// range given by `depth`, with the far plane at infinity if `far` is `None`.
+pub fn perspective_depth<S: Float, A: Angle<S>>(fovy: A, aspect: S, near: S, far: Option<S>,
+                                                depth: DepthRange) -> Mat4<S> {
+    PerspectiveFovDepth {
+        fovy:   fovy,
+        aspect: aspect,
+        near:   near,
+        far:    far,
+        depth:  depth,
+    }.to_mat4()
+}
+
+/// Create a perspective matrix from a view frustum, that maps depth values to
+/// the range given by `depth`, with the far plane at infinity if `far` is
+/// `None`.
+pub fn frustum_depth<S: Float>(left: S, right: S, bottom: S, top: S, near: S, far: Option<S>,
+                               depth: DepthRange) -> Mat4<S> {
+    PerspectiveDepth {
+        left:   left,
+        right:  right,
+        bottom: bottom,
+        top:    top,
+        near:   near,
+        far:    far,
+        depth:  depth,
+    }.to_mat4()
+}
+
+/// The range that depth values are mapped to in normalized device
+/// coordinates.
+#[deriving(Clone, Eq)]
+pub enum DepthRange {
+    /// The near and far planes map to `-1` and `1` respectively, as in OpenGL.
+    NegativeOneToOne,
+    /// The near and far planes map to `0` and `1` respectively, as in
+    /// Direct3D and Vulkan.
+    ZeroToOne,
+    /// The near and far planes map to `1` and `0` respectively. This is known
+    /// as 'reversed-Z', and distributes floating point depth precision more
+    /// evenly over the view distance.
+    OneToZero,
+}
+
+impl DepthRange {
+    /// Convert a depth value in normalized device coordinates to a window
+    /// depth in the range `[0, 1]`.
+    pub fn ndc_to_window<S: Float>(&self, z: S) -> S {
+        match *self {
+            NegativeOneToOne => (z + one()) * cast(0.5).unwrap(),
+            ZeroToOne | OneToZero => z,
+        }
+    }
+
+    /// Convert a window depth in the range `[0, 1]` to a depth value in
+    /// normalized device coordinates.
+    pub fn window_to_ndc<S: Float>(&self, z: S) -> S {
+        match *self {
+            NegativeOneToOne => z * cast(2).unwrap() - one(),
+            ZeroToOne | OneToZero => z,
+        }
+    }
+}
+
+/// A rectangular region of the window, in pixels.
+#[deriving(Clone, Eq)]
+pub struct Viewport<S> {
+    x:      S,  y:      S,
+    width:  S,  height: S,
+}
+
+impl<S: Float> Viewport<S> {
+    #[inline]
+    pub fn new(x: S, y: S, width: S, height: S) -> Viewport<S> {
+        Viewport { x: x, y: y, width: width, height: height }
+    }
+
+    /// Convert a point in normalized device coordinates to window coordinates.
+    pub fn ndc_to_window(&self, ndc: &Vec3<S>, depth: DepthRange) -> Point3<S> {
+        let half: S = cast(0.5).unwrap();
+        Point3::new(self.x + (ndc.x + one()) * half * self.width,
+                    self.y + (ndc.y + one()) * half * self.height,
+                    depth.ndc_to_window(ndc.z.clone()))
+    }
+
+    /// Convert a point in window coordinates to normalized device coordinates.
+    pub fn window_to_ndc(&self, window: &Point3<S>, depth: DepthRange) -> Vec3<S> {
+        let two: S = cast(2).unwrap();
+        Vec3::new((window.x - self.x) * two / self.width - one(),
+                  (window.y - self.y) * two / self.height - one(),
+                  depth.window_to_ndc(window.z.clone()))
+    }
+}
+
+/// Map a point from world coordinates to window coordinates, where the `z`
+/// component of the result is the window depth in the range `[0, 1]`.
+///
+/// This is the equivalent of the [gluProject]
+/// (http://www.opengl.org/sdk/docs/man2/xhtml/gluProject.xml) function, with
+/// `depth` specifying the clip space depth range that `proj` was built for.
+pub fn project<S: Float>(point: &Point3<S>, view: &Mat4<S>, proj: &Mat4<S>,
+                         viewport: &Viewport<S>, depth: DepthRange) -> Point3<S> {
+    let v = Vec4::new(point.x.clone(), point.y.clone(), point.z.clone(), one());
+    let clip = proj.mul_m(view).mul_v(&v);
+    let ndc = Vec3::new(clip.x / clip.w, clip.y / clip.w, clip.z / clip.w);
+    viewport.ndc_to_window(&ndc, depth)
+}
+
+/// Map a point from window coordinates back to world coordinates, where the
+/// `z` component of `window` is the window depth in the range `[0, 1]`.
+///
+/// This is the equivalent of the [gluUnProject]
+/// (http://www.opengl.org/sdk/docs/man2/xhtml/gluUnProject.xml) function.
+///
+/// # Return value
+///
+/// The point in world coordinates, or `None` if the combined view and
+/// projection matrix cannot be inverted, or if the point is at infinity.
+pub fn unproject<S: Float>(window: &Point3<S>, view: &Mat4<S>, proj: &Mat4<S>,
+                           viewport: &Viewport<S>, depth: DepthRange) -> Option<Point3<S>> {
+    let ndc = viewport.window_to_ndc(window, depth);
+    proj.mul_m(view).invert().and_then(|inv| {
+        let p = inv.mul_v(&Vec4::new(ndc.x.clone(), ndc.y.clone(), ndc.z.clone(), one()));
+        if p.w.approx_eq(&zero()) {
+            None
+        } else {
+            Some(Point3::new(p.x / p.w, p.y / p.w, p.z / p.w))
+        }
+    })
+}
+
+/// Create a ray in world coordinates that passes through the window
+/// coordinates `x` and `y`, for picking objects under the cursor. The ray
+/// starts on the near plane and points away from the viewer.
+pub fn pick_ray<S: Float>(x: S, y: S, view: &Mat4<S>, proj: &Mat4<S>,
+                          viewport: &Viewport<S>, depth: DepthRange) -> Option<Ray3<S>> {
+    // The second point is taken from halfway through the depth range rather
+    // than the far plane, which may be at infinity.
+    let near_z = match depth { OneToZero => one(), _ => zero() };
+    let near = unproject(&Point3::new(x.clone(), y.clone(), near_z), view, proj, viewport, depth.clone());
+    let mid = unproject(&Point3::new(x, y, cast(0.5).unwrap()), view, proj, viewport, depth);
+    match (near, mid) {
+        (Some(near), Some(mid)) => Some(Ray3::new(near.clone(), mid.sub_p(&near).normalize())),
+        _ => None,
+    }
+}
+
+/// The element at `index` of the [Halton sequence]
+/// (http://en.wikipedia.org/wiki/Halton_sequence) with the given `base`, a
+/// low-discrepancy sequence of values in the range `[0, 1)`.
+pub fn halton<S: Float>(index: uint, base: uint) -> S {
+    let b: S = cast(base).unwrap();
+    let mut f: S = one();
+    let mut result: S = zero();
+    let mut i = index;
+    while i > 0 {
+        f = f / b;
+        result = result + f * cast(i % base).unwrap();
+        i = i / base;
+    }
+    result
+}
+
+/// A sub-pixel jitter offset in the range `[-0.5, 0.5)` for frame `index`,
+/// taken from the Halton sequences with bases 2 and 3. The sequence starts at
+/// `index + 1`, because the first element of every Halton sequence is zero.
+pub fn halton_jitter<S: Float>(index: uint) -> (S, S) {
+    let half: S = cast(0.5).unwrap();
+    (halton::<S>(index + 1, 2) - half, halton::<S>(index + 1, 3) - half)
+}
+
+/// Replace the near plane of a projection matrix with an arbitrary clipping
+/// plane, using [Eric Lengyel's technique]
+/// (http://www.terathon.com/lengyel/Lengyel-Oblique.pdf). This is useful for
+/// rendering planar reflections and portals, where the geometry in front of
+/// the mirror or portal must be clipped away.
+///
+/// `plane` is in view space, and must face away from the viewer: points `p`
+/// where `n·p + d > 0` are kept, and `d` must be negative. The far plane of the
+/// resulting projection is skewed so that it still encloses the original view
+/// frustum, which costs some depth precision.
+///
+/// # Return value
+///
+/// The modified projection matrix, or `None` if `proj` cannot be inverted.
+pub fn oblique_near_plane<S: Float>(proj: &Mat4<S>, plane: &Plane<S>, depth: DepthRange) -> Option<Mat4<S>> {
+    let c = Vec4::new(plane.n.x.clone(), plane.n.y.clone(), plane.n.z.clone(), plane.d.clone());
+    proj.invert().map(|inv| {
+        // the corner of the far plane opposite the clipping plane
+        let far_z = match depth { OneToZero => zero(), _ => one() };
+        let q = inv.mul_v(&Vec4::new(sign(c.x.clone()), sign(c.y.clone()), far_z, one()));
+        let scale = one::<S>() / c.dot(&q);
+
+        // scale the plane so that `q` still lies on the far plane
+        let r3 = proj.r(3);
+        let r2 = match depth {
+            NegativeOneToOne => c.mul_s(scale * cast(2).unwrap()).sub_v(&r3),
+            ZeroToOne        => c.mul_s(scale),
+            OneToZero        => r3.sub_v(&c.mul_s(scale)),
+        };
+
+        let mut mat = proj.clone();
+        for i in range(0u, 4) {
+            *mat.mut_cr(i, 2) = r2.i(i).clone();
+        }
+        mat
+    })
+}
+
+#[inline]
+fn sign<S: Float>(x: S) -> S {
+    if x > zero() { one() } else if x < zero() { -one::<S>() } else { zero() }
+}
+
+/// The ways in which the parameters of a projection can be invalid.
+#[deriving(Clone, Eq)]
+pub enum ProjectionError {
+    /// The vertical field of view was less than or equal to zero.
+    NegativeFov,
+    /// The vertical field of view was greater than or equal to a half turn.
+    FovOverHalfTurn,
+    /// The aspect ratio was less than or equal to zero.
+    NegativeAspect,
+    /// The near plane distance of a perspective projection was less than or
+    /// equal to zero.
+    NegativeNear,
+    /// The near plane was not closer than the far plane.
+    NearBeyondFar,
+    /// The left plane was not to the left of the right plane.
+    LeftBeyondRight,
+    /// The bottom plane was not below the top plane.
+    BottomBeyondTop,
+}
+
+impl ToStr for ProjectionError {
+    fn to_str(&self) -> ~str {
+        match *self {
+            NegativeFov     => ~"The vertical field of view must be greater than zero",
+            FovOverHalfTurn => ~"The vertical field of view must be less than a half turn",
+            NegativeAspect  => ~"The aspect ratio must be greater than zero",
+            NegativeNear    => ~"The near plane distance must be greater than zero",
+            NearBeyondFar   => ~"The near plane must be closer than the far plane",
+            LeftBeyondRight => ~"`left` must be less than `right`",
+            BottomBeyondTop => ~"`bottom` must be less than `top`",
+        }
+    }
+}
+
+/// Fails with the error message if the projection parameters are invalid.
+fn expect_valid(result: Result<(), ProjectionError>) {
+    match result {
+        Ok(()) => (),
+        Err(e) => fail!(e.to_str()),
+    }
+}
+
+fn validate_fov<S: Float, A: Angle<S>>(fovy: &A, aspect: &S) -> Result<(), ProjectionError> {
+    let half_turn: A = Angle::turn_div_2();
+    if *fovy <= zero()         { Err(NegativeFov) }
+    else if *fovy >= half_turn { Err(FovOverHalfTurn) }
+    else if *aspect <= zero()  { Err(NegativeAspect) }
+    else                       { Ok(()) }
+}
+
+fn validate_depth<S: Float>(near: &S, far: &S) -> Result<(), ProjectionError> {
+    if *near >= *far { Err(NearBeyondFar) } else { Ok(()) }
+}
+
+fn validate_perspective_depth<S: Float>(near: &S, far: &S) -> Result<(), ProjectionError> {
+    if *near <= zero() { Err(NegativeNear) } else { validate_depth(near, far) }
+}
+
+fn validate_extents<S: Float>(left: &S, right: &S, bottom: &S, top: &S) -> Result<(), ProjectionError> {
+    if *left >= *right       { Err(LeftBeyondRight) }
+    else if *bottom >= *top  { Err(BottomBeyondTop) }
+    else                     { Ok(()) }
+}
+
 pub trait Projection<S>: ToMat4<S> {
     fn to_frustum(&self) -> Frustum<S>;
 }
@@ -77,6 +359,20 @@ pub struct PerspectiveFov<S, A> {
 }
 
 impl<S: Float, A: Angle<S>> PerspectiveFov<S, A> {
+    /// Construct a perspective projection, checking that the parameters are
+    /// valid.
+    pub fn new(fovy: A, aspect: S, near: S, far: S) -> Result<PerspectiveFov<S, A>, ProjectionError> {
+        let proj = PerspectiveFov { fovy: fovy, aspect: aspect, near: near, far: far };
+        proj.validate().map(|_| proj.clone())
+    }
+
+    /// Check that the parameters of the projection are valid.
+    pub fn validate(&self) -> Result<(), ProjectionError> {
+        validate_fov(&self.fovy, &self.aspect).and_then(|_| {
+            validate_perspective_depth(&self.near, &self.far)
+        })
+    }
+
     pub fn to_perspective(&self) -> Perspective<S> {
         let angle = self.fovy.div_s(cast(2).unwrap());
         let ymax = self.near * tan(angle);
@@ -91,6 +387,18 @@ impl<S: Float, A: Angle<S>> PerspectiveFov<S, A> {
             far:     self.far.clone(),
         }
     }
+
+    /// Offset the projection by a sub-pixel amount, for temporal
+    /// anti-aliasing. See `Perspective::jitter`.
+    pub fn jitter(&self, x: S, y: S, width: S, height: S) -> Perspective<S> {
+        self.to_perspective().jitter(x, y, width, height)
+    }
+
+    /// Build an off-axis stereo pair of projections. See
+    /// `Perspective::stereo`.
+    pub fn stereo(&self, ipd: S, convergence: S) -> (Perspective<S>, Perspective<S>) {
+        self.to_perspective().stereo(ipd, convergence)
+    }
 }
 
 impl<S: Float, A: Angle<S>> Projection<S> for PerspectiveFov<S, A> {
@@ -102,14 +410,7 @@ impl<S: Float, A: Angle<S>> Projection<S> for PerspectiveFov<S, A> {
 
 impl<S: Float, A: Angle<S>> ToMat4<S> for PerspectiveFov<S, A> {
     fn to_mat4(&self) -> Mat4<S> {
-        let half_turn: A = Angle::turn_div_2();
-
-        assert!(self.fovy   < zero(),    "The vertical field of view cannot be below zero");
-        assert!(self.fovy   > half_turn, "The vertical field of view cannot be greater than a half turn");
-        assert!(self.aspect < zero(),    "The aspect ratio cannot be below zero");
-        assert!(self.near   < zero(),    "The near plane distance cannot be below zero");
-        assert!(self.far    < zero(),    "The far plane distance cannot be below zero");
-        assert!(self.far    < self.near, "The far plane cannot be closer than the near plane");
+        expect_valid(self.validate());
 
         let f = cot(self.fovy.div_s(cast(2).unwrap()));
         let two: S = cast(2).unwrap();
@@ -141,6 +442,79 @@ impl<S: Float, A: Angle<S>> ToMat4<S> for PerspectiveFov<S, A> {
     }
 }
 
+/// A perspective projection based on a vertical field-of-view angle, with a
+/// choice of depth range and an optional far plane.
+///
+/// Setting `far` to `None` places the far plane at infinity. Combined with a
+/// `depth` of `OneToZero`, this gives the reversed-Z infinite projection that
+/// is commonly used for rendering large scenes.
+#[deriving(Clone, Eq)]
+pub struct PerspectiveFovDepth<S, A> {
+    fovy:   A,
+    aspect: S,
+    near:   S,
+    far:    Option<S>,
+    depth:  DepthRange,
+}
+
+impl<S: Float, A: Angle<S>> PerspectiveFovDepth<S, A> {
+    /// Construct a perspective projection, checking that the parameters are
+    /// valid.
+    pub fn new(fovy: A, aspect: S, near: S, far: Option<S>,
+               depth: DepthRange) -> Result<PerspectiveFovDepth<S, A>, ProjectionError> {
+        let proj = PerspectiveFovDepth { fovy: fovy, aspect: aspect, near: near, far: far, depth: depth };
+        proj.validate().map(|_| proj.clone())
+    }
+
+    /// Check that the parameters of the projection are valid.
+    pub fn validate(&self) -> Result<(), ProjectionError> {
+        validate_fov(&self.fovy, &self.aspect).and_then(|_| {
+            match self.far {
+                Some(ref far) => validate_perspective_depth(&self.near, far),
+                None if self.near <= zero() => Err(NegativeNear),
+                None => Ok(()),
+            }
+        })
+    }
+}
+
+impl<S: Float, A: Angle<S>> Projection<S> for PerspectiveFovDepth<S, A> {
+    fn to_frustum(&self) -> Frustum<S> {
+        Frustum::from_mat4_depth(self.to_mat4(), self.depth.clone())
+    }
+}
+
+/// The third row of a perspective matrix, which maps view space depth to
+/// clip space depth, as the elements in the third and fourth columns.
+fn perspective_depth_terms<S: Float>(near: S, far: Option<S>, depth: DepthRange) -> (S, S) {
+    let n = near;
+    let two: S = cast(2).unwrap();
+
+    match (depth, far) {
+        (NegativeOneToOne, Some(far)) => ((far + n) / (n - far), (two * far * n) / (n - far)),
+        (NegativeOneToOne, None)      => (-one::<S>(), -two * n),
+        (ZeroToOne, Some(far))        => (far / (n - far), (far * n) / (n - far)),
+        (ZeroToOne, None)             => (-one::<S>(), -n),
+        (OneToZero, Some(far))        => (n / (far - n), (far * n) / (far - n)),
+        (OneToZero, None)             => (zero(), n),
+    }
+}
+
+impl<S: Float, A: Angle<S>> ToMat4<S> for PerspectiveFovDepth<S, A> {
+    fn to_mat4(&self) -> Mat4<S> {
+        expect_valid(self.validate());
+
+        let f = cot(self.fovy.div_s(cast(2).unwrap()));
+        let (c2r2, c3r2) = perspective_depth_terms(self.near.clone(), self.far.clone(),
+                                                   self.depth.clone());
+
+        Mat4::new(f / self.aspect, zero(), zero(), zero(),
+                  zero(), f, zero(), zero(),
+                  zero(), zero(), c2r2, -one::<S>(),
+                  zero(), zero(), c3r2, zero())
+    }
+}
+
 /// A perspective projection with arbitrary left/right/bottom/top distances
 #[deriving(Clone, Eq)]
 pub struct Perspective<S> {
@@ -149,6 +523,85 @@ pub struct Perspective<S> {
     near:   S,  far:    S,
 }
 
+impl<S: Float> Perspective<S> {
+    /// Construct a perspective projection, checking that the parameters are
+    /// valid.
+    pub fn new(left: S, right: S, bottom: S, top: S, near: S, far: S) -> Result<Perspective<S>, ProjectionError> {
+        let proj = Perspective { left: left, right: right, bottom: bottom, top: top, near: near, far: far };
+        proj.validate().map(|_| proj.clone())
+    }
+
+    /// Check that the parameters of the projection are valid.
+    pub fn validate(&self) -> Result<(), ProjectionError> {
+        validate_extents(&self.left, &self.right, &self.bottom, &self.top).and_then(|_| {
+            validate_perspective_depth(&self.near, &self.far)
+        })
+    }
+
+    /// The corners of the view frustum, in view space.
+    pub fn to_points(&self) -> FrustumPoints<S> {
+        let scale = self.far / self.near;
+        let (l, r) = (self.left.clone(), self.right.clone());
+        let (b, t) = (self.bottom.clone(), self.top.clone());
+        let (n, f) = (-self.near, -self.far);
+        FrustumPoints {
+            near_top_left:     Point3::new(l.clone(), t.clone(), n.clone()),
+            near_top_right:    Point3::new(r.clone(), t.clone(), n.clone()),
+            near_bottom_left:  Point3::new(l.clone(), b.clone(), n.clone()),
+            near_bottom_right: Point3::new(r.clone(), b.clone(), n.clone()),
+            far_top_left:      Point3::new(l * scale, t * scale, f.clone()),
+            far_top_right:     Point3::new(r * scale, t * scale, f.clone()),
+            far_bottom_left:   Point3::new(l * scale, b * scale, f.clone()),
+            far_bottom_right:  Point3::new(r * scale, b * scale, f.clone()),
+        }
+    }
+
+    /// Offset the projection so that the image moves by `x` and `y` pixels,
+    /// for a viewport of `width` by `height` pixels. This is used to jitter the
+    /// projection by a different sub-pixel amount each frame for temporal
+    /// anti-aliasing, usually with the offsets from `halton_jitter`.
+    pub fn jitter(&self, x: S, y: S, width: S, height: S) -> Perspective<S> {
+        let dx = x * (self.right - self.left) / width;
+        let dy = y * (self.top - self.bottom) / height;
+        Perspective {
+            left:   self.left - dx,
+            right:  self.right - dx,
+            bottom: self.bottom - dy,
+            top:    self.top - dy,
+            near:   self.near.clone(),
+            far:    self.far.clone(),
+        }
+    }
+
+    /// Build an off-axis stereo pair of projections for the left and right
+    /// eyes, separated by the interpupillary distance `ipd`, that converge at
+    /// a distance of `convergence` in front of the viewer.
+    ///
+    /// The eyes are assumed to be at `-ipd / 2` and `ipd / 2` along the `x`
+    /// axis of view space, so the view matrix for each eye must be translated
+    /// accordingly.
+    pub fn stereo(&self, ipd: S, convergence: S) -> (Perspective<S>, Perspective<S>) {
+        let shift = ipd * cast(0.5).unwrap() * self.near / convergence;
+        let left = Perspective {
+            left:   self.left + shift,
+            right:  self.right + shift,
+            bottom: self.bottom.clone(),
+            top:    self.top.clone(),
+            near:   self.near.clone(),
+            far:    self.far.clone(),
+        };
+        let right = Perspective {
+            left:   self.left - shift,
+            right:  self.right - shift,
+            bottom: self.bottom.clone(),
+            top:    self.top.clone(),
+            near:   self.near.clone(),
+            far:    self.far.clone(),
+        };
+        (left, right)
+    }
+}
+
 impl<S: Float> Projection<S> for Perspective<S> {
     fn to_frustum(&self) -> Frustum<S> {
         // TODO: Could this be faster?
@@ -158,9 +611,7 @@ impl<S: Float> Projection<S> for Perspective<S> {
 
 impl<S: Float> ToMat4<S> for Perspective<S> {
     fn to_mat4(&self) -> Mat4<S> {
-        assert!(self.left   > self.right, "`left` cannot be greater than `right");
-        assert!(self.bottom > self.top,   "`bottom` cannot be greater than `top");
-        assert!(self.near   > self.far,   "`near` cannot be greater than `far");
+        expect_valid(self.validate());
 
         let two: S = cast(2).unwrap();
 
@@ -191,6 +642,65 @@ impl<S: Float> ToMat4<S> for Perspective<S> {
     }
 }
 
+/// A perspective projection with arbitrary left/right/bottom/top distances,
+/// with a choice of depth range and an optional far plane. See
+/// `PerspectiveFovDepth`.
+#[deriving(Clone, Eq)]
+pub struct PerspectiveDepth<S> {
+    left:   S,  right:  S,
+    bottom: S,  top:    S,
+    near:   S,  far:    Option<S>,
+    depth:  DepthRange,
+}
+
+impl<S: Float> PerspectiveDepth<S> {
+    /// Construct a perspective projection, checking that the parameters are
+    /// valid.
+    pub fn new(left: S, right: S, bottom: S, top: S, near: S, far: Option<S>,
+               depth: DepthRange) -> Result<PerspectiveDepth<S>, ProjectionError> {
+        let proj = PerspectiveDepth { left: left, right: right, bottom: bottom, top: top,
+                                      near: near, far: far, depth: depth };
+        proj.validate().map(|_| proj.clone())
+    }
+
+    /// Check that the parameters of the projection are valid.
+    pub fn validate(&self) -> Result<(), ProjectionError> {
+        validate_extents(&self.left, &self.right, &self.bottom, &self.top).and_then(|_| {
+            match self.far {
+                Some(ref far) => validate_perspective_depth(&self.near, far),
+                None if self.near <= zero() => Err(NegativeNear),
+                None => Ok(()),
+            }
+        })
+    }
+}
+
+impl<S: Float> Projection<S> for PerspectiveDepth<S> {
+    fn to_frustum(&self) -> Frustum<S> {
+        Frustum::from_mat4_depth(self.to_mat4(), self.depth.clone())
+    }
+}
+
+impl<S: Float> ToMat4<S> for PerspectiveDepth<S> {
+    fn to_mat4(&self) -> Mat4<S> {
+        expect_valid(self.validate());
+
+        let two: S = cast(2).unwrap();
+        let (c2r2, c3r2) = perspective_depth_terms(self.near.clone(), self.far.clone(),
+                                                   self.depth.clone());
+
+        let c0r0 = (two * self.near) / (self.right - self.left);
+        let c1r1 = (two * self.near) / (self.top - self.bottom);
+        let c2r0 = (self.right + self.left) / (self.right - self.left);
+        let c2r1 = (self.top + self.bottom) / (self.top - self.bottom);
+
+        Mat4::new(c0r0, zero(), zero(), zero(),
+                  zero(), c1r1, zero(), zero(),
+                  c2r0, c2r1, c2r2, -one::<S>(),
+                  zero(), zero(), c3r2, zero())
+    }
+}
+
 /// An orthographic projection with arbitrary left/right/bottom/top distances
 #[deriving(Clone, Eq)]
 pub struct Ortho<S> {
@@ -199,24 +709,39 @@ pub struct Ortho<S> {
     near:   S,  far:    S,
 }
 
+impl<S: Float> Ortho<S> {
+    /// Construct an orthographic projection, checking that the parameters are
+    /// valid.
+    pub fn new(left: S, right: S, bottom: S, top: S, near: S, far: S) -> Result<Ortho<S>, ProjectionError> {
+        let proj = Ortho { left: left, right: right, bottom: bottom, top: top, near: near, far: far };
+        proj.validate().map(|_| proj.clone())
+    }
+
+    /// Check that the parameters of the projection are valid. Unlike a
+    /// perspective projection, the near plane may be behind the viewer.
+    pub fn validate(&self) -> Result<(), ProjectionError> {
+        validate_extents(&self.left, &self.right, &self.bottom, &self.top).and_then(|_| {
+            validate_depth(&self.near, &self.far)
+        })
+    }
+}
+
 impl<S: Float> Projection<S> for Ortho<S> {
     fn to_frustum(&self) -> Frustum<S> {
         Frustum {
-            left:   Plane::from_abcd( one::<S>(), zero::<S>(), zero::<S>(), self.left.clone()),
+            left:   Plane::from_abcd( one::<S>(), zero::<S>(), zero::<S>(), -self.left),
             right:  Plane::from_abcd(-one::<S>(), zero::<S>(), zero::<S>(), self.right.clone()),
-            bottom: Plane::from_abcd(zero::<S>(),  one::<S>(), zero::<S>(), self.bottom.clone()),
+            bottom: Plane::from_abcd(zero::<S>(),  one::<S>(), zero::<S>(), -self.bottom),
             top:    Plane::from_abcd(zero::<S>(), -one::<S>(), zero::<S>(), self.top.clone()),
-            near:   Plane::from_abcd(zero::<S>(), zero::<S>(), -one::<S>(), self.near.clone()),
-            far:    Plane::from_abcd(zero::<S>(), zero::<S>(),  one::<S>(), self.far.clone()),
+            near:   Plane::from_abcd(zero::<S>(), zero::<S>(), -one::<S>(), -self.near),
+            far:    Some(Plane::from_abcd(zero::<S>(), zero::<S>(),  one::<S>(), self.far.clone())),
         }
     }
 }
 
 impl<S: Float> ToMat4<S> for Ortho<S> {
     fn to_mat4(&self) -> Mat4<S> {
-        assert!(self.left   > self.right, "`left` cannot be greater than `right");
-        assert!(self.bottom > self.top,   "`bottom` cannot be greater than `top");
-        assert!(self.near   > self.far,   "`near` cannot be greater than `far");
+        expect_valid(self.validate());
 
         let two: S = cast(2).unwrap();
 
@@ -233,7 +758,7 @@ impl<S: Float> ToMat4<S> for Ortho<S> {
         let c2r0 = zero();
         let c2r1 = zero();
         let c2r2 = -two / (self.far - self.near);
-        let c2r3 = -one::<S>();
+        let c2r3 = zero();
 
         let c3r0 = -(self.right + self.left) / (self.right - self.left);
         let c3r1 = -(self.top + self.bottom) / (self.top - self.bottom);
@@ -13,19 +13,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::from_str::FromStr;
 use std::num::{zero, one, cast, sqrt};
 
-use angle::{Angle, Rad, acos, cos, sin, sin_cos};
+use angle::{Angle, Rad, acos, atan2, cos, sin, sin_cos};
+use approx::{RelativeEq, UlpsEq, default_epsilon};
 use array::{Array, build};
-use matrix::{Mat3, ToMat3};
+use format::{ToStrDigits, format_list, parse_list};
+use matrix::{Matrix, Mat3, ToMat3};
 use vector::{Vec3, Vector, EuclideanVector};
 
 /// A quaternion in scalar/vector form
+#[repr(C)]
 #[deriving(Clone, Eq)]
 pub struct Quat<S> { s: S, v: Vec3<S> }
 
 array!(impl<S> Quat<S> -> [S, ..4] _4)
 approx_eq!(impl<S> Quat<S>)
+format_array!(impl<S> Quat<S>, 4)
 
 pub trait ToQuat<S: Float> {
     fn to_quat(&self) -> Quat<S>;
@@ -95,6 +100,91 @@ impl<S: Float> Quat<S> {
                       axis.mul_s(sin(half)))
     }
 
+    /// Decompose the quaternion into a twist around `axis`, and the swing that
+    /// remains, such that `self == swing.mul_q(&twist)`. Both the quaternion
+    /// and `axis` should be normalized.
+    ///
+    /// # Return value
+    ///
+    /// A tuple of the form `(swing, twist)`. If the quaternion is a half turn
+    /// around an axis perpendicular to `axis` the twist is undefined, and the
+    /// identity is returned in its place.
+    pub fn swing_twist(&self, axis: &Vec3<S>) -> (Quat<S>, Quat<S>) {
+        let projection = axis.mul_s(self.v.dot(axis));
+        let twist = match Quat::from_sv(self.s.clone(), projection).renormalize() {
+            Some(twist) => twist,
+            None => Quat::identity(),
+        };
+        (self.mul_q(&twist.conjugate()), twist)
+    }
+
+    /// Limit the angle of the rotation to `max`, keeping the same axis. When
+    /// applied to the swing of a `swing_twist` decomposition this acts as a
+    /// cone limit.
+    pub fn limit_angle<A: Angle<S>>(&self, max: A) -> Quat<S> {
+        let q = if self.s < zero() { -*self } else { self.clone() };
+        let length = q.v.length();
+        let theta: Rad<S> = atan2(length.clone(), q.s.clone());
+
+        if theta.mul_s(cast(2).unwrap()) > max.to_rad() && !length.approx_eq(&zero()) {
+            Quat::from_axis_angle(&q.v.div_s(length), max)
+        } else {
+            q
+        }
+    }
+
+    /// Limit the signed angle of the twist around `axis` to the range
+    /// `[min, max]`, keeping the swing unchanged.
+    pub fn limit_twist<A: Angle<S>>(&self, axis: &Vec3<S>, min: A, max: A) -> Quat<S> {
+        let (swing, twist) = self.swing_twist(axis);
+        swing.mul_q(&twist_clamp(&twist, axis, min, max))
+    }
+
+    /// Limit the swing of the rotation to a cone of half-angle `max_swing`
+    /// around `axis`, and the twist around `axis` to the range
+    /// `[min_twist, max_twist]`. This is useful for enforcing joint limits.
+    pub fn limit_swing_twist<A: Angle<S>>(&self, axis: &Vec3<S>, max_swing: A,
+                                          min_twist: A, max_twist: A) -> Quat<S> {
+        let (swing, twist) = self.swing_twist(axis);
+        swing.limit_angle(max_swing)
+             .mul_q(&twist_clamp(&twist, axis, min_twist, max_twist))
+    }
+
+    /// Create the quaternion representing the shortest rotation that takes the
+    /// direction of `src` to the direction of `dst`. Neither vector needs to
+    /// be normalized.
+    ///
+    /// If the vectors point in opposite directions the rotation axis is not
+    /// unique, so a half turn around an arbitrary axis perpendicular to `src`
+    /// is returned.
+    pub fn from_arc(src: &Vec3<S>, dst: &Vec3<S>) -> Quat<S> {
+        let a = src.normalize();
+        let b = dst.normalize();
+        let dot = a.dot(&b);
+
+        if dot.approx_eq(&-one::<S>()) {
+            Quat::from_sv(zero(), perpendicular(&a))
+        } else {
+            // This is the quaternion halfway between the identity and the
+            // rotation by twice the angle, which avoids computing the angle
+            // and stays accurate for nearly parallel vectors.
+            Quat::from_sv(one::<S>() + dot, a.cross(&b)).normalize()
+        }
+    }
+
+    /// Create a quaternion that rotates the direction of `src` to the
+    /// direction of `dst`, while keeping `up` pointing as close to up as
+    /// possible. Unlike `from_arc` this will not introduce a roll around
+    /// `dst`, which is useful for turning a model to face a target.
+    ///
+    /// Falls back to `from_arc` if either direction is parallel to `up`.
+    pub fn from_arc_up(src: &Vec3<S>, dst: &Vec3<S>, up: &Vec3<S>) -> Quat<S> {
+        match (upright_frame(src, up), upright_frame(dst, up)) {
+            (Some(from), Some(to)) => to.mul_m(&from.transpose()).to_quat(),
+            _ => Quat::from_arc(src, dst),
+        }
+    }
+
     /// The additive identity, ie: `q = 0 + 0i + 0j + 0i`
     #[inline]
     pub fn zero() -> Quat<S> {
@@ -135,7 +225,7 @@ impl<S: Float> Quat<S> {
     /// The difference between this quaternion and `other`
     #[inline]
     pub fn sub_q(&self, other: &Quat<S>) -> Quat<S> {
-        build(|i| self.i(i).add(other.i(i)))
+        build(|i| self.i(i).sub(other.i(i)))
     }
 
     /// The the result of multipliplying the quaternion by `other`
@@ -209,6 +299,30 @@ impl<S: Float> Quat<S> {
         self.mul_s(one::<S>() / self.magnitude())
     }
 
+    /// Returns `true` if the quaternion has a magnitude of approximately `1`.
+    #[inline]
+    pub fn is_normalized(&self) -> bool {
+        self.magnitude2().approx_eq(&one())
+    }
+
+    /// Normalize the quaternion, checking that it can be done safely. This is
+    /// useful for correcting the drift accumulated after concatenating many
+    /// rotations.
+    ///
+    /// # Return value
+    ///
+    /// The normalized quaternion, or `None` if the magnitude of the quaternion
+    /// is too close to zero for it to represent a rotation.
+    #[inline]
+    pub fn renormalize(&self) -> Option<Quat<S>> {
+        let mag2 = self.magnitude2();
+        if mag2.approx_eq(&zero()) {
+            None
+        } else {
+            Some(self.mul_s(one::<S>() / sqrt(mag2)))
+        }
+    }
+
     /// Normalised linear interpolation
     ///
     /// # Return value
@@ -223,7 +337,9 @@ impl<S: Float> Quat<S> {
     /// Spherical Linear Intoperlation
     ///
     /// Perform a spherical linear interpolation between the quaternion and
-    /// `other`. Both quaternions should be normalized first.
+    /// `other`. Both quaternions should be normalized first. If the
+    /// quaternions are in opposite hemispheres `other` is negated, so that
+    /// the interpolation takes the shortest path between the rotations.
     ///
     /// # Return value
     ///
@@ -240,8 +356,15 @@ impl<S: Float> Quat<S> {
     /// - [Arcsynthesis OpenGL tutorial]
     ///   (http://www.arcsynthesis.org/gltut/Positioning/Tut08%20Interpolation.html)
     pub fn slerp(&self, other: &Quat<S>, amount: S) -> Quat<S> {
-        use std::num::cast;
+        if self.dot(other) < zero() {
+            self.slerp_unchecked(&-*other, amount)
+        } else {
+            self.slerp_unchecked(other, amount)
+        }
+    }
 
+    /// Spherical linear interpolation without the shortest path correction.
+    fn slerp_unchecked(&self, other: &Quat<S>, amount: S) -> Quat<S> {
         let dot = self.dot(other);
         let dot_threshold = cast(0.9995).unwrap();
 
@@ -262,6 +385,156 @@ impl<S: Float> Quat<S> {
                 .add_q(&q.mul_s(sin(theta)))
         }
     }
+
+    /// Spherical quadrangle interpolation
+    ///
+    /// Interpolate between the keyframe `self` and the following keyframe
+    /// `other`, using the intermediate control points `a` and `b` computed by
+    /// `squad_control` for each keyframe. Unlike `slerp`, the resulting curve
+    /// has a continuous first derivative when passing through keyframes.
+    ///
+    /// - [Ken Shoemake, Animating Rotation with Quaternion Curves]
+    ///   (http://www.cs.cmu.edu/~kiranb/animation/p245-shoemake.pdf)
+    pub fn squad(&self, other: &Quat<S>, a: &Quat<S>, b: &Quat<S>, amount: S) -> Quat<S> {
+        let two: S = cast(2).unwrap();
+        let p = self.slerp_unchecked(other, amount.clone());
+        let q = a.slerp_unchecked(b, amount.clone());
+        p.slerp_unchecked(&q, two * amount * (one::<S>() - amount))
+    }
+
+    /// The intermediate control point used by `squad` for the keyframe
+    /// `self`, given the keyframes before and after it. All of the keyframes
+    /// should be normalized.
+    pub fn squad_control(&self, prev: &Quat<S>, next: &Quat<S>) -> Quat<S> {
+        let quarter: S = cast(0.25).unwrap();
+        let prev = if self.dot(prev) < zero() { -*prev } else { prev.clone() };
+        let next = if self.dot(next) < zero() { -*next } else { next.clone() };
+        let inv = self.conjugate();
+
+        let ln_next = inv.mul_q(&next).ln();
+        let ln_prev = inv.mul_q(&prev).ln();
+        self.mul_q(&ln_next.add_q(&ln_prev).mul_s(-quarter).exp())
+    }
+
+    /// The exponential of the quaternion.
+    pub fn exp(&self) -> Quat<S> {
+        let length = self.v.length();
+        let exp_s = self.s.exp();
+
+        if length.approx_eq(&zero()) {
+            Quat::from_sv(exp_s.clone(), self.v.mul_s(exp_s))
+        } else {
+            let (sin, cos) = length.sin_cos();
+            Quat::from_sv(exp_s * cos, self.v.mul_s(exp_s * sin / length))
+        }
+    }
+
+    /// The natural logarithm of the quaternion. For a unit quaternion the
+    /// result has a scalar part of zero, and a vector part equal to the axis
+    /// of rotation scaled by half of the rotation angle.
+    ///
+    /// If the vector part is zero the axis is undefined, and the vector part
+    /// of the result is zero. This includes negative real quaternions, whose
+    /// logarithm could be any axis scaled by `π`.
+    pub fn ln(&self) -> Quat<S> {
+        let length = self.v.length();
+        let ln_mag = self.magnitude().ln();
+
+        // `atan2(length, s) / length` tends to `1 / s` as the vector part
+        // vanishes, but only when the scalar part is positive
+        let scale = if self.s > zero() && length.approx_eq(&zero()) {
+            one::<S>() / self.s
+        } else if length == zero() {
+            zero()
+        } else {
+            length.atan2(&self.s) / length
+        };
+        Quat::from_sv(ln_mag, self.v.mul_s(scale))
+    }
+
+    /// Raise the quaternion to the power of `exponent`. For a unit quaternion
+    /// this scales the angle of the rotation.
+    #[inline]
+    pub fn powf(&self, exponent: S) -> Quat<S> {
+        self.ln().mul_s(exponent).exp()
+    }
+
+    /// The angle of the smallest rotation that takes `self` to `other`. Both
+    /// quaternions should be normalized.
+    pub fn angle_between(&self, other: &Quat<S>) -> Rad<S> {
+        let two: S = cast(2).unwrap();
+        let diff = self.conjugate().mul_q(other);
+        let theta: Rad<S> = atan2(diff.v.length(), diff.s.abs());
+        theta.mul_s(two)
+    }
+
+    /// Advance the orientation by a constant angular velocity over a timestep,
+    /// using the exact exponential map. The angular velocity is given in the
+    /// world frame, as an axis scaled by the rate of rotation in radians.
+    pub fn integrate(&self, omega: &Vec3<S>, dt: S) -> Quat<S> {
+        let half: S = cast(0.5).unwrap();
+        Quat::from_sv(zero(), omega.mul_s(half * dt))
+            .exp()
+            .mul_q(self)
+    }
+
+    /// Advance the orientation by an angular velocity over a timestep, using a
+    /// first order approximation of `integrate`. This is cheaper, but is only
+    /// accurate when the rotation over the timestep is small.
+    pub fn integrate_approx(&self, omega: &Vec3<S>, dt: S) -> Quat<S> {
+        let half: S = cast(0.5).unwrap();
+        let spin = Quat::from_sv(zero(), omega.clone()).mul_q(self);
+        self.add_q(&spin.mul_s(half * dt)).normalize()
+    }
+
+    /// The constant angular velocity in the world frame that rotates `self` to
+    /// `other` over the timestep `dt`, following the shortest path. This is
+    /// the inverse of `integrate`.
+    pub fn angular_velocity(&self, other: &Quat<S>, dt: S) -> Vec3<S> {
+        let two: S = cast(2).unwrap();
+        let diff = other.mul_q(&self.conjugate());
+        let diff = if diff.s < zero() { -diff } else { diff };
+        diff.ln().v.mul_s(two / dt)
+    }
+}
+
+/// Clamp the signed angle of a twist around `axis` to `[min, max]`.
+fn twist_clamp<S: Float, A: Angle<S>>(twist: &Quat<S>, axis: &Vec3<S>, min: A, max: A) -> Quat<S> {
+    let q = if twist.s < zero() { -*twist } else { twist.clone() };
+    let theta: Rad<S> = atan2(q.v.dot(axis), q.s.clone());
+    let theta = theta.mul_s(cast(2).unwrap());
+    let (min, max) = (min.to_rad(), max.to_rad());
+
+    if theta < min {
+        Quat::from_axis_angle(axis, min)
+    } else if theta > max {
+        Quat::from_axis_angle(axis, max)
+    } else {
+        q
+    }
+}
+
+/// A unit vector perpendicular to the unit vector `v`.
+fn perpendicular<S: Float>(v: &Vec3<S>) -> Vec3<S> {
+    let x = Vec3::unit_x().cross(v);
+    if x.length2().approx_eq(&zero()) {
+        Vec3::unit_y().cross(v).normalize()
+    } else {
+        x.normalize()
+    }
+}
+
+/// An orthonormal basis with `dir` as its first column and the component of
+/// `up` perpendicular to `dir` as its second, or `None` if they are parallel.
+fn upright_frame<S: Float>(dir: &Vec3<S>, up: &Vec3<S>) -> Option<Mat3<S>> {
+    let dir = dir.normalize();
+    let side = dir.cross(up);
+    if side.length2().approx_eq(&zero()) {
+        None
+    } else {
+        let side = side.normalize();
+        Some(Mat3::from_cols(dir.clone(), side.cross(&dir), side))
+    }
 }
 
 impl<S: Float> ToMat3<S> for Quat<S> {
@@ -295,9 +568,3 @@ impl<S: Float> Neg<Quat<S>> for Quat<S> {
         Quat::from_sv(-self.s, -self.v)
     }
 }
-
-impl<S> ToStr for Quat<S> {
-    fn to_str(&self) -> ~str {
-        fmt!("%? + %?i + %?j + %?k", self.s, self.v.x, self.v.y, self.v.z)
-    }
-}
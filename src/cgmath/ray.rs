@@ -13,6 +13,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::from_str::{FromStr, from_str};
+
+use format::{ToStrDigits, format_list, split_list};
 use point::{Point2, Point3};
 use vector::{Vec2, Vec3};
 
@@ -43,3 +46,6 @@ impl<S> Ray3<S> {
         Ray3 { origin: origin, direction: direction }
     }
 }
+
+format_fields!(impl<S> Ray2 { origin, direction })
+format_fields!(impl<S> Ray3 { origin, direction })
@@ -13,14 +13,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use angle::Angle;
+use std::from_str::{FromStr, from_str};
+use std::num::{zero, one, cast, sqrt};
+
+use angle::{Angle, rad, sin_cos};
+use approx::{RelativeEq, UlpsEq, default_epsilon};
+use array::build;
+use format::{ToStrDigits, format_list, split_list};
 use matrix::Matrix;
 use matrix::{Mat2, ToMat2};
 use matrix::{Mat3, ToMat3};
 use point::{Point2, Point3};
 use quaternion::{Quat, ToQuat};
 use ray::{Ray2, Ray3};
-use vector::{Vector, Vec2, Vec3};
+use vector::{Vector, EuclideanVector, Vec2, Vec3};
 
 /// A two-dimensional rotation
 pub trait Rotation2
@@ -55,6 +61,15 @@ pub trait Rotation3
     fn rotate_point3(&self, point: &Point3<S>) -> Point3<S>;
     fn rotate_vec3(&self, vec: &Vec3<S>) -> Vec3<S>;
     fn rotate_ray3(&self, ray: &Ray3<S>) -> Ray3<S>;
+
+    /// Rotate every point in `src`, writing the results to `dst`, which must
+    /// be the same length as `src`.
+    fn rotate_points3(&self, src: &[Point3<S>], dst: &mut [Point3<S>]);
+
+    /// Rotate every vector in `src`, writing the results to `dst`, which must
+    /// be the same length as `src`.
+    fn rotate_vecs3(&self, src: &[Vec3<S>], dst: &mut [Vec3<S>]);
+
     fn concat(&self, other: &Self) -> Self;
     fn concat_self(&mut self, other: &Self);
     fn invert(&self) -> Self;
@@ -73,6 +88,12 @@ pub struct Rot2<S> {
 }
 
 impl<S: Float> Rot2<S> {
+    /// Create a rotation matrix from a counter-clockwise rotation by `theta`.
+    pub fn from_angle<A: Angle<S>>(theta: A) -> Rot2<S> {
+        let (s, c) = sin_cos(theta);
+        Rot2 { mat: Mat2::new(c.clone(), s.clone(), -s, c) }
+    }
+
     #[inline]
     pub fn as_mat2<'a>(&'a self) -> &'a Mat2<S> { &'a self.mat }
 }
@@ -93,13 +114,17 @@ impl<S: Float> ToMat2<S> for Rot2<S> {
 
 impl<S: Float> Rotation2<S> for Rot2<S> {
     #[inline]
-    fn rotate_point2(&self, _point: &Point2<S>) -> Point2<S> { fail!("Not yet implemented") }
+    fn rotate_point2(&self, point: &Point2<S>) -> Point2<S> {
+        Point2::from_vec(&self.mat.mul_v(&point.to_vec()))
+    }
 
     #[inline]
     fn rotate_vec2(&self, vec: &Vec2<S>) -> Vec2<S> { self.mat.mul_v(vec) }
 
     #[inline]
-    fn rotate_ray2(&self, _ray: &Ray2<S>) -> Ray2<S> { fail!("Not yet implemented") }
+    fn rotate_ray2(&self, ray: &Ray2<S>) -> Ray2<S> {
+        Ray2::new(self.rotate_point2(&ray.origin), self.rotate_vec2(&ray.direction))
+    }
 
     #[inline]
     fn concat(&self, other: &Rot2<S>) -> Rot2<S> { Rot2 { mat: self.mat.mul_m(&other.mat) } }
@@ -107,32 +132,35 @@ impl<S: Float> Rotation2<S> for Rot2<S> {
     #[inline]
     fn concat_self(&mut self, other: &Rot2<S>) { self.mat.mul_self_m(&other.mat); }
 
-    // TODO: we know the matrix is orthogonal, so this could be re-written
-    // to be faster
+    // The matrix is orthogonal, so the inverse is equal to the transpose
     #[inline]
-    fn invert(&self) -> Rot2<S> { Rot2 { mat: self.mat.invert().unwrap() } }
+    fn invert(&self) -> Rot2<S> { Rot2 { mat: self.mat.transpose() } }
 
-    // TODO: we know the matrix is orthogonal, so this could be re-written
-    // to be faster
     #[inline]
-    fn invert_self(&mut self) { self.mat.invert_self(); }
+    fn invert_self(&mut self) { self.mat.transpose_self(); }
 }
 
-impl<S: Float> ApproxEq<S> for Rot2<S> {
-    #[inline]
-    fn approx_epsilon() -> S {
-        // TODO: fix this after static methods are fixed in rustc
-        fail!(~"Doesn't work!");
-    }
+approx_eq_fields!(impl<S> Rot2<S> { mat })
 
-    #[inline]
-    fn approx_eq(&self, other: &Rot2<S>) -> bool {
-        self.mat.approx_eq(&other.mat)
-    }
+impl<S: Clone + ToStr> ToStr for Rot2<S> {
+    fn to_str(&self) -> ~str { self.mat.to_str() }
+}
 
-    #[inline]
-    fn approx_eq_eps(&self, other: &Rot2<S>, approx_epsilon: &S) -> bool {
-        self.mat.approx_eq_eps(&other.mat, approx_epsilon)
+impl<S: Clone + ToStrDigits> ToStrDigits for Rot2<S> {
+    fn to_str_digits(&self, digits: uint) -> ~str { self.mat.to_str_digits(digits) }
+}
+
+impl<S: Float + FromStr> FromStr for Rot2<S> {
+    /// Parses a matrix, which is only accepted if it is a rotation: it must be
+    /// orthonormal, with a positive determinant.
+    fn from_str(s: &str) -> Option<Rot2<S>> {
+        from_str::<Mat2<S>>(s).and_then(|mat| {
+            if mat.mul_m(&mat.transpose()).is_identity() && mat.determinant() > zero() {
+                Some(Rot2 { mat: mat })
+            } else {
+                None
+            }
+        })
     }
 }
 
@@ -184,8 +212,59 @@ impl<S: Float> Rot3<S> {
         Rot3 { mat: Mat3::from_axis_angle(axis, angle) }
     }
 
+    /// Create the shortest rotation that takes the direction of `src` to the
+    /// direction of `dst`. See `Quat::from_arc` for details.
+    #[inline]
+    pub fn between_vectors(src: &Vec3<S>, dst: &Vec3<S>) -> Rot3<S> {
+        Quat::from_arc(src, dst).to_rot3()
+    }
+
+    /// Create a rotation that takes the direction of `src` to the direction of
+    /// `dst` without introducing a roll. See `Quat::from_arc_up` for details.
+    #[inline]
+    pub fn between_vectors_up(src: &Vec3<S>, dst: &Vec3<S>, up: &Vec3<S>) -> Rot3<S> {
+        Quat::from_arc_up(src, dst, up).to_rot3()
+    }
+
+    /// Advance the rotation by a constant angular velocity over a timestep.
+    /// See `Quat::integrate` for details.
+    #[inline]
+    pub fn integrate(&self, omega: &Vec3<S>, dt: S) -> Rot3<S> {
+        self.to_quat().integrate(omega, dt).to_rot3()
+    }
+
+    /// The constant angular velocity that rotates `self` to `other` over the
+    /// timestep `dt`. See `Quat::angular_velocity` for details.
+    #[inline]
+    pub fn angular_velocity(&self, other: &Rot3<S>, dt: S) -> Vec3<S> {
+        self.to_quat().angular_velocity(&other.to_quat(), dt)
+    }
+
     #[inline]
     pub fn as_mat3<'a>(&'a self) -> &'a Mat3<S> { &'a self.mat }
+
+    /// Re-orthonormalize the rotation matrix using Gram-Schmidt
+    /// orthogonalization. Repeated calls to `concat_self` accumulate floating
+    /// point error, so this should be called periodically to keep the matrix
+    /// a valid rotation.
+    pub fn orthonormalize(&self) -> Rot3<S> {
+        let x = self.mat.x.normalize();
+        let y = self.mat.y.sub_v(&x.mul_s(x.dot(&self.mat.y))).normalize();
+        let z = x.cross(&y);
+        Rot3 { mat: Mat3::from_cols(x, y, z) }
+    }
+
+    /// Re-orthonormalize the rotation matrix in place.
+    #[inline]
+    pub fn orthonormalize_self(&mut self) {
+        *self = self.orthonormalize();
+    }
+
+    /// Returns `true` if the columns of the matrix are of unit length and
+    /// perpendicular to each other.
+    pub fn is_orthonormal(&self) -> bool {
+        self.mat.mul_m(&self.mat.transpose()).is_identity()
+    }
 }
 
 pub trait ToRot3<S: Float> {
@@ -217,6 +296,16 @@ impl<S: Float> Rotation3<S> for Rot3<S> {
     #[inline]
     fn rotate_ray3(&self, _ray: &Ray3<S>) -> Ray3<S> { fail!("Not yet implemented") }
 
+    #[inline]
+    fn rotate_points3(&self, src: &[Point3<S>], dst: &mut [Point3<S>]) {
+        self.mat.transform_points(src, dst)
+    }
+
+    #[inline]
+    fn rotate_vecs3(&self, src: &[Vec3<S>], dst: &mut [Vec3<S>]) {
+        self.mat.transform_vectors(src, dst)
+    }
+
     #[inline]
     fn concat(&self, other: &Rot3<S>) -> Rot3<S> { Rot3 { mat: self.mat.mul_m(&other.mat) } }
 
@@ -234,21 +323,27 @@ impl<S: Float> Rotation3<S> for Rot3<S> {
     fn invert_self(&mut self) { self.mat.invert_self(); }
 }
 
-impl<S: Float> ApproxEq<S> for Rot3<S> {
-    #[inline]
-    fn approx_epsilon() -> S {
-        // TODO: fix this after static methods are fixed in rustc
-        fail!(~"Doesn't work!");
-    }
+approx_eq_fields!(impl<S> Rot3<S> { mat })
 
-    #[inline]
-    fn approx_eq(&self, other: &Rot3<S>) -> bool {
-        self.mat.approx_eq(&other.mat)
-    }
+impl<S: Clone + ToStr> ToStr for Rot3<S> {
+    fn to_str(&self) -> ~str { self.mat.to_str() }
+}
 
-    #[inline]
-    fn approx_eq_eps(&self, other: &Rot3<S>, approx_epsilon: &S) -> bool {
-        self.mat.approx_eq_eps(&other.mat, approx_epsilon)
+impl<S: Clone + ToStrDigits> ToStrDigits for Rot3<S> {
+    fn to_str_digits(&self, digits: uint) -> ~str { self.mat.to_str_digits(digits) }
+}
+
+impl<S: Float + FromStr> FromStr for Rot3<S> {
+    /// Parses a matrix, which is only accepted if it is a rotation: it must be
+    /// orthonormal, with a positive determinant.
+    fn from_str(s: &str) -> Option<Rot3<S>> {
+        from_str::<Mat3<S>>(s).and_then(|mat| {
+            if mat.mul_m(&mat.transpose()).is_identity() && mat.determinant() > zero() {
+                Some(Rot3 { mat: mat })
+            } else {
+                None
+            }
+        })
     }
 }
 
@@ -274,6 +369,16 @@ impl<S: Float> Rotation3<S> for Quat<S> {
     #[inline]
     fn rotate_ray3(&self, _ray: &Ray3<S>) -> Ray3<S> { fail!("Not yet implemented") }
 
+    #[inline]
+    fn rotate_points3(&self, src: &[Point3<S>], dst: &mut [Point3<S>]) {
+        self.to_mat3().transform_points(src, dst)
+    }
+
+    #[inline]
+    fn rotate_vecs3(&self, src: &[Vec3<S>], dst: &mut [Vec3<S>]) {
+        self.to_mat3().transform_vectors(src, dst)
+    }
+
     #[inline]
     fn concat(&self, other: &Quat<S>) -> Quat<S> { self.mul_q(other) }
 
@@ -286,3 +391,414 @@ impl<S: Float> Rotation3<S> for Quat<S> {
     #[inline]
     fn invert_self(&mut self) { *self = self.invert() }
 }
+
+// Axis angle rotations
+
+/// A rotation of `angle` around `axis`, following the right hand rule. The
+/// axis is expected to be of unit length.
+#[deriving(Clone, Eq)]
+pub struct AxisAngle<S, A> {
+    axis: Vec3<S>,
+    angle: A,
+}
+
+impl<S: Float, A: Angle<S>> AxisAngle<S, A> {
+    #[inline]
+    pub fn new(axis: Vec3<S>, angle: A) -> AxisAngle<S, A> {
+        AxisAngle { axis: axis, angle: angle }
+    }
+
+    /// Extract the axis and angle of the rotation represented by a quaternion.
+    /// The resulting angle is in the range `[0, half_turn]`.
+    ///
+    /// The angle is computed with `atan2` rather than `acos`, so precision is
+    /// retained for rotations close to zero or a half turn. If the rotation is
+    /// too small for the axis to be determined, the `x` axis is returned.
+    pub fn from_quat(quat: &Quat<S>) -> AxisAngle<S, A> {
+        // `q` and `-q` represent the same rotation, so pick the one that
+        // rotates by less than a half turn
+        let q = if quat.s < zero() { -*quat } else { quat.clone() };
+        let sin_half = q.v.length();
+        let two: S = cast(2).unwrap();
+        let angle: A = Angle::from(rad(sin_half.atan2(&q.s) * two));
+
+        if sin_half.approx_eq(&zero()) {
+            AxisAngle::new(Vec3::unit_x(), angle)
+        } else {
+            AxisAngle::new(q.v.div_s(sin_half), angle)
+        }
+    }
+
+    /// Extract the axis and angle of the rotation represented by a rotation
+    /// matrix.
+    #[inline]
+    pub fn from_mat3(mat: &Mat3<S>) -> AxisAngle<S, A> {
+        AxisAngle::from_quat(&mat.to_quat())
+    }
+
+    /// Construct a rotation from a scaled axis (also known as a rotation
+    /// vector), where the direction of the vector is the axis of rotation and
+    /// its length is the angle in radians. This is the usual representation
+    /// of angular velocities.
+    pub fn from_scaled_axis(v: &Vec3<S>) -> AxisAngle<S, A> {
+        let length = v.length();
+        if length.approx_eq(&zero()) {
+            AxisAngle::new(Vec3::unit_x(), Angle::from(rad(zero::<S>())))
+        } else {
+            AxisAngle::new(v.div_s(length.clone()), Angle::from(rad(length)))
+        }
+    }
+
+    /// The scaled axis representation of the rotation, where the length of
+    /// the vector is the angle in radians.
+    #[inline]
+    pub fn to_scaled_axis(&self) -> Vec3<S> {
+        self.axis.mul_s(self.angle.to_rad().s.clone())
+    }
+}
+
+impl<S: Float, A: Angle<S>> ToQuat<S> for AxisAngle<S, A> {
+    #[inline]
+    fn to_quat(&self) -> Quat<S> { Quat::from_axis_angle(&self.axis, self.angle.clone()) }
+}
+
+impl<S: Float, A: Angle<S>> ToMat3<S> for AxisAngle<S, A> {
+    #[inline]
+    fn to_mat3(&self) -> Mat3<S> { Mat3::from_axis_angle(&self.axis, self.angle.clone()) }
+}
+
+impl<S: Float, A: Angle<S>> ToRot3<S> for AxisAngle<S, A> {
+    #[inline]
+    fn to_rot3(&self) -> Rot3<S> { Rot3 { mat: self.to_mat3() } }
+}
+
+impl<S: Float, A: Angle<S>> Rotation3<S> for AxisAngle<S, A> {
+    #[inline]
+    fn rotate_point3(&self, point: &Point3<S>) -> Point3<S> {
+        Point3::from_vec(&self.rotate_vec3(&point.to_vec()))
+    }
+
+    fn rotate_vec3(&self, vec: &Vec3<S>) -> Vec3<S> {
+        // Rodrigues' rotation formula
+        let (s, c) = sin_cos(self.angle.clone());
+        vec.mul_s(c.clone())
+           .add_v(&self.axis.cross(vec).mul_s(s))
+           .add_v(&self.axis.mul_s(self.axis.dot(vec) * (one::<S>() - c)))
+    }
+
+    #[inline]
+    fn rotate_ray3(&self, ray: &Ray3<S>) -> Ray3<S> {
+        Ray3::new(self.rotate_point3(&ray.origin), self.rotate_vec3(&ray.direction))
+    }
+
+    #[inline]
+    fn rotate_points3(&self, src: &[Point3<S>], dst: &mut [Point3<S>]) {
+        self.to_mat3().transform_points(src, dst)
+    }
+
+    #[inline]
+    fn rotate_vecs3(&self, src: &[Vec3<S>], dst: &mut [Vec3<S>]) {
+        self.to_mat3().transform_vectors(src, dst)
+    }
+
+    #[inline]
+    fn concat(&self, other: &AxisAngle<S, A>) -> AxisAngle<S, A> {
+        AxisAngle::from_quat(&self.to_quat().mul_q(&other.to_quat()))
+    }
+
+    #[inline]
+    fn concat_self(&mut self, other: &AxisAngle<S, A>) { *self = self.concat(other); }
+
+    #[inline]
+    fn invert(&self) -> AxisAngle<S, A> { AxisAngle::new(self.axis.clone(), -self.angle) }
+
+    #[inline]
+    fn invert_self(&mut self) { self.angle.neg_self(); }
+}
+
+impl<S: Float, A: Angle<S>> ApproxEq<S> for AxisAngle<S, A> {
+    #[inline]
+    fn approx_epsilon() -> S {
+        default_epsilon()
+    }
+
+    #[inline]
+    fn approx_eq(&self, other: &AxisAngle<S, A>) -> bool {
+        self.axis.approx_eq(&other.axis) &&
+        self.angle.approx_eq(&other.angle)
+    }
+
+    #[inline]
+    fn approx_eq_eps(&self, other: &AxisAngle<S, A>, approx_epsilon: &S) -> bool {
+        self.axis.approx_eq_eps(&other.axis, approx_epsilon) &&
+        self.angle.approx_eq_eps(&other.angle, approx_epsilon)
+    }
+}
+
+impl<S: Float + RelativeEq<S>, A: Angle<S> + RelativeEq<S>> RelativeEq<S> for AxisAngle<S, A> {
+    #[inline]
+    fn relative_eq(&self, other: &AxisAngle<S, A>, epsilon: &S, max_relative: &S) -> bool {
+        self.axis.relative_eq(&other.axis, epsilon, max_relative) &&
+        self.angle.relative_eq(&other.angle, epsilon, max_relative)
+    }
+}
+
+impl<S: Float + UlpsEq, A: Angle<S> + UlpsEq> UlpsEq for AxisAngle<S, A> {
+    #[inline]
+    fn ulps_eq(&self, other: &AxisAngle<S, A>, max_ulps: uint) -> bool {
+        self.axis.ulps_eq(&other.axis, max_ulps) &&
+        self.angle.ulps_eq(&other.angle, max_ulps)
+    }
+}
+
+impl<S: Clone + ToStr, A: ToStr> ToStr for AxisAngle<S, A> {
+    /// Writes the rotation as a list of its axis and angle.
+    fn to_str(&self) -> ~str {
+        format_list([self.axis.to_str(), self.angle.to_str()])
+    }
+}
+
+impl<S: Clone + ToStrDigits, A: ToStrDigits> ToStrDigits for AxisAngle<S, A> {
+    fn to_str_digits(&self, digits: uint) -> ~str {
+        format_list([self.axis.to_str_digits(digits), self.angle.to_str_digits(digits)])
+    }
+}
+
+impl<S: Clone + FromStr, A: FromStr> FromStr for AxisAngle<S, A> {
+    fn from_str(s: &str) -> Option<AxisAngle<S, A>> {
+        split_list(s).and_then(|parts| {
+            if parts.len() != 2 { return None; }
+            match (from_str(parts[0]), from_str(parts[1])) {
+                (Some(axis), Some(angle)) => Some(AxisAngle { axis: axis, angle: angle }),
+                _ => None,
+            }
+        })
+    }
+}
+
+// Euler angles
+
+/// The sequence of axes that a set of euler angles rotate around, listed in
+/// the order that the rotations are applied.
+///
+/// The first six orders are Tait-Bryan angles, which rotate around three
+/// distinct axes. The remaining six are proper euler angles, where the first
+/// and last rotations are around the same axis.
+#[deriving(Clone, Eq)]
+pub enum EulerOrder {
+    XYZ, XZY, YXZ, YZX, ZXY, ZYX,
+    XYX, XZX, YXY, YZY, ZXZ, ZYZ,
+}
+
+impl EulerOrder {
+    /// The indices of the three axes, where `0`, `1` and `2` correspond to the
+    /// `x`, `y` and `z` axes respectively.
+    pub fn axes(&self) -> (uint, uint, uint) {
+        match *self {
+            XYZ => (0, 1, 2), XZY => (0, 2, 1),
+            YXZ => (1, 0, 2), YZX => (1, 2, 0),
+            ZXY => (2, 0, 1), ZYX => (2, 1, 0),
+            XYX => (0, 1, 0), XZX => (0, 2, 0),
+            YXY => (1, 0, 1), YZY => (1, 2, 1),
+            ZXZ => (2, 0, 2), ZYZ => (2, 1, 2),
+        }
+    }
+}
+
+impl ToStr for EulerOrder {
+    fn to_str(&self) -> ~str {
+        match *self {
+            XYZ => ~"XYZ", XZY => ~"XZY",
+            YXZ => ~"YXZ", YZX => ~"YZX",
+            ZXY => ~"ZXY", ZYX => ~"ZYX",
+            XYX => ~"XYX", XZX => ~"XZX",
+            YXY => ~"YXY", YZY => ~"YZY",
+            ZXZ => ~"ZXZ", ZYZ => ~"ZYZ",
+        }
+    }
+}
+
+impl FromStr for EulerOrder {
+    fn from_str(s: &str) -> Option<EulerOrder> {
+        match s.trim() {
+            "XYZ" => Some(XYZ), "XZY" => Some(XZY),
+            "YXZ" => Some(YXZ), "YZX" => Some(YZX),
+            "ZXY" => Some(ZXY), "ZYX" => Some(ZYX),
+            "XYX" => Some(XYX), "XZX" => Some(XZX),
+            "YXY" => Some(YXY), "YZY" => Some(YZY),
+            "ZXZ" => Some(ZXZ), "ZYZ" => Some(ZYZ),
+            _ => None,
+        }
+    }
+}
+
+/// The reference frame that the rotations of a set of euler angles are
+/// applied in.
+#[deriving(Clone, Eq)]
+pub enum EulerFrame {
+    /// Each rotation is applied around the axes of the body, as rotated by
+    /// the preceding rotations.
+    Intrinsic,
+    /// Each rotation is applied around the fixed axes of the reference frame.
+    Extrinsic,
+}
+
+impl ToStr for EulerFrame {
+    fn to_str(&self) -> ~str {
+        match *self {
+            Intrinsic => ~"Intrinsic",
+            Extrinsic => ~"Extrinsic",
+        }
+    }
+}
+
+impl FromStr for EulerFrame {
+    fn from_str(s: &str) -> Option<EulerFrame> {
+        match s.trim() {
+            "Intrinsic" => Some(Intrinsic),
+            "Extrinsic" => Some(Extrinsic),
+            _ => None,
+        }
+    }
+}
+
+/// A rotation represented by three successive rotations around the axes
+/// given by `order`.
+///
+/// # Fields
+///
+/// - `a`: the angle of the first rotation
+/// - `b`: the angle of the second rotation
+/// - `c`: the angle of the third rotation
+/// - `order`: the axes that are rotated around
+/// - `frame`: whether the rotations are intrinsic or extrinsic
+///
+/// # Notes
+///
+/// An intrinsic rotation is equivalent to the extrinsic rotation with the
+/// order of the rotations reversed. For example `Mat3::from_euler(x, y, z)` is
+/// equivalent to `Euler::new(x, y, z, XYZ, Extrinsic)`, which is the same
+/// rotation as `Euler::new(z, y, x, ZYX, Intrinsic)`.
+#[deriving(Clone, Eq)]
+pub struct Euler<A> {
+    a: A,
+    b: A,
+    c: A,
+    order: EulerOrder,
+    frame: EulerFrame,
+}
+
+impl<S: Float, A: Angle<S>> Euler<A> {
+    #[inline]
+    pub fn new(a: A, b: A, c: A, order: EulerOrder, frame: EulerFrame) -> Euler<A> {
+        Euler { a: a, b: b, c: c, order: order, frame: frame }
+    }
+
+    /// Extract a set of euler angles from a rotation matrix.
+    ///
+    /// When the second rotation aligns the axes of the first and third
+    /// rotations (gimbal lock), only the sum or difference of their angles is
+    /// defined. In this case the third angle is set to zero.
+    pub fn from_mat3(mat: &Mat3<S>, order: EulerOrder, frame: EulerFrame) -> Euler<A> {
+        // Ken Shoemake, "Euler Angle Conversion", Graphics Gems IV, pp. 222-229
+        //
+        // Intrinsic rotations are solved as extrinsic rotations in the
+        // reverse order, swapping the first and third angles at the end.
+        let (first, second, third) = order.axes();
+        let i = match frame { Extrinsic => first, Intrinsic => third };
+        let j = second;
+        let k = 3 - i - j;
+        let odd = j != (i + 1) % 3;
+
+        // element at row `r`, column `c`
+        let m = |r: uint, c: uint| -> S { mat.cr(c, r).clone() };
+
+        let (x, y, z) = if first == third {
+            let sy = sqrt(m(i, j) * m(i, j) + m(i, k) * m(i, k));
+            if sy.approx_eq(&zero()) {
+                ((-m(j, k)).atan2(&m(j, j)), sy.atan2(&m(i, i)), zero::<S>())
+            } else {
+                (m(i, j).atan2(&m(i, k)), sy.atan2(&m(i, i)), m(j, i).atan2(&-m(k, i)))
+            }
+        } else {
+            let cy = sqrt(m(i, i) * m(i, i) + m(j, i) * m(j, i));
+            if cy.approx_eq(&zero()) {
+                ((-m(j, k)).atan2(&m(j, j)), (-m(k, i)).atan2(&cy), zero::<S>())
+            } else {
+                (m(k, j).atan2(&m(k, k)), (-m(k, i)).atan2(&cy), m(j, i).atan2(&m(i, i)))
+            }
+        };
+
+        let (x, y, z) = if odd { (-x, -y, -z) } else { (x, y, z) };
+        let (a, b, c) = match frame { Extrinsic => (x, y, z), Intrinsic => (z, y, x) };
+
+        Euler::new(Angle::from(rad(a)), Angle::from(rad(b)), Angle::from(rad(c)), order, frame)
+    }
+
+    /// Extract a set of euler angles from a quaternion.
+    #[inline]
+    pub fn from_quat(quat: &Quat<S>, order: EulerOrder, frame: EulerFrame) -> Euler<A> {
+        Euler::from_mat3(&quat.to_mat3(), order, frame)
+    }
+}
+
+/// The unit vector along the axis with index `i`.
+#[inline]
+fn unit_axis<S: Float>(i: uint) -> Vec3<S> {
+    build(|n| if n == i { one::<S>() } else { zero::<S>() })
+}
+
+impl<S: Float, A: Angle<S>> ToQuat<S> for Euler<A> {
+    fn to_quat(&self) -> Quat<S> {
+        let (i, j, k) = self.order.axes();
+        let qa = Quat::from_axis_angle(&unit_axis(i), self.a.clone());
+        let qb = Quat::from_axis_angle(&unit_axis(j), self.b.clone());
+        let qc = Quat::from_axis_angle(&unit_axis(k), self.c.clone());
+
+        match self.frame {
+            Intrinsic => qa.mul_q(&qb).mul_q(&qc),
+            Extrinsic => qc.mul_q(&qb).mul_q(&qa),
+        }
+    }
+}
+
+impl<S: Float, A: Angle<S>> ToMat3<S> for Euler<A> {
+    #[inline]
+    fn to_mat3(&self) -> Mat3<S> { self.to_quat().to_mat3() }
+}
+
+impl<S: Float, A: Angle<S>> ToRot3<S> for Euler<A> {
+    #[inline]
+    fn to_rot3(&self) -> Rot3<S> { Rot3 { mat: self.to_mat3() } }
+}
+
+impl<A: ToStr> ToStr for Euler<A> {
+    /// Writes the rotation as a list of its three angles, followed by its
+    /// order and frame, for example `[30°, 0°, 90°, ZYX, Intrinsic]`.
+    fn to_str(&self) -> ~str {
+        format_list([self.a.to_str(), self.b.to_str(), self.c.to_str(),
+                     self.order.to_str(), self.frame.to_str()])
+    }
+}
+
+impl<A: ToStrDigits> ToStrDigits for Euler<A> {
+    fn to_str_digits(&self, digits: uint) -> ~str {
+        format_list([self.a.to_str_digits(digits), self.b.to_str_digits(digits),
+                     self.c.to_str_digits(digits), self.order.to_str(), self.frame.to_str()])
+    }
+}
+
+impl<A: FromStr> FromStr for Euler<A> {
+    fn from_str(s: &str) -> Option<Euler<A>> {
+        split_list(s).and_then(|parts| {
+            if parts.len() != 5 { return None; }
+            match (from_str(parts[0]), from_str(parts[1]), from_str(parts[2]),
+                   from_str(parts[3]), from_str(parts[4])) {
+                (Some(a), Some(b), Some(c), Some(order), Some(frame)) => {
+                    Some(Euler { a: a, b: b, c: c, order: order, frame: frame })
+                }
+                _ => None,
+            }
+        })
+    }
+}
@@ -0,0 +1,271 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Serialization
+//!
+//! This module is opt-in, and is only built when the crate is compiled with
+//! `--cfg serialize`. It provides two encodings:
+//!
+//! - implementations of `Encodable` and `Decodable` from `extra::serialize`,
+//!   so that the types can be used with any of its encoders, such as
+//!   `extra::json` and `extra::ebml`.
+//! - a raw little-endian byte encoding, via the `LeBytes` trait.
+//!
+//! # Layout
+//!
+//! The layout is part of the public interface, and will not change:
+//!
+//! - `Vec2`, `Vec3`, `Vec4`, `Point2` and `Point3` are a sequence of their
+//!   components, in the order `x`, `y`, `z`, `w`.
+//! - `Quat` is a sequence of its components, in the order `s`, `x`, `y`, `z`.
+//! - matrices are a sequence of their columns, each a sequence of scalars, in
+//!   the same column major order they are stored in.
+//! - `Rad` and `Deg` are a single scalar.
+//! - `Plane`, `Aabb2`, `Aabb3`, `Sphere`, `Ray2` and `Ray3` are structs with
+//!   the fields `n` and `d`, `center` and `size`, `center` and `radius`, and
+//!   `origin` and `direction` respectively, in that order.
+//!
+//! The byte encoding writes the same scalars in the same order, as IEEE 754
+//! floating point numbers in little-endian byte order, with no padding or
+//! length prefixes. So a `Vec3<f32>` is always 12 bytes long, and a
+//! `Mat4<f64>` is always 128 bytes long.
+
+use std::cast::transmute;
+use std::vec;
+
+use extra::serialize::{Encodable, Decodable, Encoder, Decoder};
+
+use aabb::{Aabb2, Aabb3};
+use angle::{Rad, Deg};
+use array::Array;
+use matrix::{Mat2, Mat3, Mat4};
+use matrix::{Mat2x3, Mat2x4, Mat3x2, Mat3x4, Mat4x2, Mat4x3};
+use plane::Plane;
+use point::{Point2, Point3};
+use quaternion::Quat;
+use ray::{Ray2, Ray3};
+use sphere::Sphere;
+use vector::{Vec2, Vec3, Vec4};
+
+/// Conversion to and from a little-endian byte encoding.
+pub trait LeBytes {
+    /// Append the little-endian encoding of `self` to `out`.
+    fn write_le(&self, out: &mut ~[u8]);
+
+    /// Read a value from `bytes`, starting at `*pos`, and advance `*pos` past
+    /// the bytes that were read.
+    ///
+    /// # Return value
+    ///
+    /// The value, or `None` if there are not enough bytes left, in which case
+    /// the value of `*pos` is unspecified.
+    fn read_le(bytes: &[u8], pos: &mut uint) -> Option<Self>;
+}
+
+/// Encode a value as little-endian bytes.
+pub fn to_le_bytes<T: LeBytes>(x: &T) -> ~[u8] {
+    let mut out = ~[];
+    x.write_le(&mut out);
+    out
+}
+
+/// Decode a value from little-endian bytes.
+///
+/// # Return value
+///
+/// The value, or `None` if `bytes` is not exactly as long as the encoding of
+/// a `T`.
+pub fn from_le_bytes<T: LeBytes>(bytes: &[u8]) -> Option<T> {
+    let mut pos = 0u;
+    match LeBytes::read_le(bytes, &mut pos) {
+        Some(x) if pos == bytes.len() => Some(x),
+        _ => None,
+    }
+}
+
+macro_rules! float_le_bytes(
+    ($T:ty, $Bits:ty, $n:expr) => (
+        impl LeBytes for $T {
+            fn write_le(&self, out: &mut ~[u8]) {
+                let bits: $Bits = unsafe { transmute(*self) };
+                for i in range(0u, $n) {
+                    out.push((bits >> (8 * i)) as u8);
+                }
+            }
+
+            fn read_le(bytes: &[u8], pos: &mut uint) -> Option<$T> {
+                if bytes.len() < *pos + $n { return None; }
+                let mut bits: $Bits = 0;
+                for i in range(0u, $n) {
+                    bits |= (bytes[*pos + i] as $Bits) << (8 * i);
+                }
+                *pos += $n;
+                Some(unsafe { transmute(bits) })
+            }
+        }
+    )
+)
+
+float_le_bytes!(f32, u32, 4)
+float_le_bytes!(f64, u64, 8)
+
+/// Implements the serialization traits for a type that implements `Array`,
+/// which is written as a sequence of its `n` elements.
+macro_rules! serialize_array(
+    (impl<$S:ident> $Self:ty -> [$T:ty, ..$n:expr]) => (
+        impl<$S: Clone + Encodable<E>, E: Encoder> Encodable<E> for $Self {
+            fn encode(&self, e: &mut E) {
+                do e.emit_seq($n) |e| {
+                    for (i, x) in self.iter().enumerate() {
+                        e.emit_seq_elt(i, |e| x.encode(e));
+                    }
+                }
+            }
+        }
+
+        impl<$S: Clone + Decodable<D>, D: Decoder> Decodable<D> for $Self {
+            fn decode(d: &mut D) -> $Self {
+                do d.read_seq |d, len| {
+                    if len != $n {
+                        fail!("expected a sequence of %u elements, found %u", $n, len);
+                    }
+                    let xs: ~[$T] = do vec::from_fn($n) |i| {
+                        d.read_seq_elt(i, |d| Decodable::decode(d))
+                    };
+                    Array::build(|i| xs[i].clone())
+                }
+            }
+        }
+
+        impl<$S: Clone + LeBytes> LeBytes for $Self {
+            fn write_le(&self, out: &mut ~[u8]) {
+                for x in self.iter() { x.write_le(out); }
+            }
+
+            fn read_le(bytes: &[u8], pos: &mut uint) -> Option<$Self> {
+                let mut xs: ~[$T] = vec::with_capacity($n);
+                for _ in range(0u, $n) {
+                    let x: Option<$T> = LeBytes::read_le(bytes, pos);
+                    match x {
+                        Some(x) => xs.push(x),
+                        None => return None,
+                    }
+                }
+                Some(Array::build(|i| xs[i].clone()))
+            }
+        }
+    )
+)
+
+/// Implements the serialization traits for a struct, which is written as its
+/// named fields, in the order they are listed.
+macro_rules! serialize_fields(
+    (impl<$S:ident> $Self:ident { $($field:ident),+ }) => (
+        impl<$S: Clone + Encodable<E>, E: Encoder> Encodable<E> for $Self<$S> {
+            fn encode(&self, e: &mut E) {
+                let len = [$(stringify!($field)),+].len();
+                do e.emit_struct(stringify!($Self), len) |e| {
+                    let mut i = 0u;
+                    $(
+                        e.emit_struct_field(stringify!($field), i, |e| self.$field.encode(e));
+                        i += 1;
+                    )+
+                }
+            }
+        }
+
+        impl<$S: Clone + Decodable<D>, D: Decoder> Decodable<D> for $Self<$S> {
+            fn decode(d: &mut D) -> $Self<$S> {
+                let len = [$(stringify!($field)),+].len();
+                do d.read_struct(stringify!($Self), len) |d| {
+                    let mut i = 0u;
+                    $(
+                        let $field = d.read_struct_field(stringify!($field), i, |d| Decodable::decode(d));
+                        i += 1;
+                    )+
+                    $Self { $($field: $field),+ }
+                }
+            }
+        }
+
+        impl<$S: Clone + LeBytes> LeBytes for $Self<$S> {
+            fn write_le(&self, out: &mut ~[u8]) {
+                $(self.$field.write_le(out);)+
+            }
+
+            fn read_le(bytes: &[u8], pos: &mut uint) -> Option<$Self<$S>> {
+                $(
+                    let $field = match LeBytes::read_le(bytes, pos) {
+                        Some(x) => x,
+                        None => return None,
+                    };
+                )+
+                Some($Self { $($field: $field),+ })
+            }
+        }
+    )
+)
+
+/// Implements the serialization traits for an angle type, which is written as
+/// a single scalar.
+macro_rules! serialize_angle(
+    ($Self:ident) => (
+        impl<S: Encodable<E>, E: Encoder> Encodable<E> for $Self<S> {
+            fn encode(&self, e: &mut E) { self.s.encode(e) }
+        }
+
+        impl<S: Decodable<D>, D: Decoder> Decodable<D> for $Self<S> {
+            fn decode(d: &mut D) -> $Self<S> { $Self { s: Decodable::decode(d) } }
+        }
+
+        impl<S: LeBytes> LeBytes for $Self<S> {
+            fn write_le(&self, out: &mut ~[u8]) { self.s.write_le(out) }
+
+            fn read_le(bytes: &[u8], pos: &mut uint) -> Option<$Self<S>> {
+                LeBytes::read_le(bytes, pos).map(|s| $Self { s: s })
+            }
+        }
+    )
+)
+
+serialize_array!(impl<S> Vec2<S> -> [S, ..2])
+serialize_array!(impl<S> Vec3<S> -> [S, ..3])
+serialize_array!(impl<S> Vec4<S> -> [S, ..4])
+
+serialize_array!(impl<S> Point2<S> -> [S, ..2])
+serialize_array!(impl<S> Point3<S> -> [S, ..3])
+
+serialize_array!(impl<S> Quat<S> -> [S, ..4])
+
+serialize_array!(impl<S> Mat2<S> -> [Vec2<S>, ..2])
+serialize_array!(impl<S> Mat3<S> -> [Vec3<S>, ..3])
+serialize_array!(impl<S> Mat4<S> -> [Vec4<S>, ..4])
+
+serialize_array!(impl<S> Mat2x3<S> -> [Vec3<S>, ..2])
+serialize_array!(impl<S> Mat2x4<S> -> [Vec4<S>, ..2])
+serialize_array!(impl<S> Mat3x2<S> -> [Vec2<S>, ..3])
+serialize_array!(impl<S> Mat3x4<S> -> [Vec4<S>, ..3])
+serialize_array!(impl<S> Mat4x2<S> -> [Vec2<S>, ..4])
+serialize_array!(impl<S> Mat4x3<S> -> [Vec3<S>, ..4])
+
+serialize_angle!(Rad)
+serialize_angle!(Deg)
+
+serialize_fields!(impl<S> Plane { n, d })
+serialize_fields!(impl<S> Aabb2 { center, size })
+serialize_fields!(impl<S> Aabb3 { center, size })
+serialize_fields!(impl<S> Sphere { center, radius })
+serialize_fields!(impl<S> Ray2 { origin, direction })
+serialize_fields!(impl<S> Ray3 { origin, direction })
@@ -0,0 +1,235 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SIMD implementations of the hot `f32` operations on `Vec4`, `Quat` and
+//! `Mat4`.
+//!
+//! The generic implementations go through `Array::build` closures, one
+//! component at a time. These functions instead work on four lanes at once,
+//! using `std::unstable::simd::f32x4`, which is lowered to SSE2 on x86_64.
+//! On other architectures a scalar `f32x4` with the same interface is used,
+//! so the results are the same everywhere, within floating point error.
+//!
+//! The generic methods such as `Mat4::mul_m` do not use these functions,
+//! because a generic implementation cannot be replaced for a single scalar
+//! type. Callers opt in by calling them directly, for example
+//! `simd::mat4_mul_m(&a, &b)` in place of `a.mul_m(&b)`.
+
+use std::num::sqrt;
+
+#[cfg(target_arch = "x86_64")]
+use std::unstable::simd::f32x4;
+
+use matrix::Mat4;
+use quaternion::Quat;
+use vector::{Vec3, Vec4};
+
+/// A scalar stand-in for the SIMD vector type.
+#[cfg(not(target_arch = "x86_64"))]
+#[allow(non_camel_case_types)]
+struct f32x4(f32, f32, f32, f32);
+
+macro_rules! scalar_f32x4_op(
+    ($Op:ident, $op:ident) => (
+        #[cfg(not(target_arch = "x86_64"))]
+        impl $Op<f32x4, f32x4> for f32x4 {
+            #[inline]
+            fn $op(&self, other: &f32x4) -> f32x4 {
+                let f32x4(a0, a1, a2, a3) = *self;
+                let f32x4(b0, b1, b2, b3) = *other;
+                f32x4(a0.$op(&b0), a1.$op(&b1), a2.$op(&b2), a3.$op(&b3))
+            }
+        }
+    )
+)
+
+scalar_f32x4_op!(Add, add)
+scalar_f32x4_op!(Sub, sub)
+scalar_f32x4_op!(Mul, mul)
+
+#[inline]
+fn splat(x: f32) -> f32x4 { f32x4(x, x, x, x) }
+
+#[inline]
+fn load_v(v: &Vec4<f32>) -> f32x4 { f32x4(v.x, v.y, v.z, v.w) }
+
+#[inline]
+fn store_v(v: f32x4) -> Vec4<f32> {
+    let f32x4(x, y, z, w) = v;
+    Vec4::new(x, y, z, w)
+}
+
+#[inline]
+fn load_q(q: &Quat<f32>) -> f32x4 { f32x4(q.s, q.v.x, q.v.y, q.v.z) }
+
+#[inline]
+fn store_q(q: f32x4) -> Quat<f32> {
+    let f32x4(s, x, y, z) = q;
+    Quat::new(s, x, y, z)
+}
+
+/// The sum of the four lanes.
+#[inline]
+fn sum(v: f32x4) -> f32 {
+    let f32x4(a, b, c, d) = v;
+    (a + b) + (c + d)
+}
+
+/// The sum of two vectors.
+#[inline]
+pub fn vec4_add(a: &Vec4<f32>, b: &Vec4<f32>) -> Vec4<f32> {
+    store_v(load_v(a) + load_v(b))
+}
+
+/// The difference of two vectors.
+#[inline]
+pub fn vec4_sub(a: &Vec4<f32>, b: &Vec4<f32>) -> Vec4<f32> {
+    store_v(load_v(a) - load_v(b))
+}
+
+/// The component-wise product of two vectors.
+#[inline]
+pub fn vec4_mul(a: &Vec4<f32>, b: &Vec4<f32>) -> Vec4<f32> {
+    store_v(load_v(a) * load_v(b))
+}
+
+/// The product of a vector and a scalar.
+#[inline]
+pub fn vec4_mul_s(a: &Vec4<f32>, s: f32) -> Vec4<f32> {
+    store_v(load_v(a) * splat(s))
+}
+
+/// The dot product of two vectors.
+#[inline]
+pub fn vec4_dot(a: &Vec4<f32>, b: &Vec4<f32>) -> f32 {
+    sum(load_v(a) * load_v(b))
+}
+
+/// The vector scaled to a length of one.
+#[inline]
+pub fn vec4_normalize(a: &Vec4<f32>) -> Vec4<f32> {
+    let v = load_v(a);
+    store_v(v * splat(1.0 / sqrt(sum(v * v))))
+}
+
+/// The product of two quaternions.
+#[inline]
+pub fn quat_mul_q(a: &Quat<f32>, b: &Quat<f32>) -> Quat<f32> {
+    let Quat { s: s, v: Vec3 { x: x, y: y, z: z } } = *b;
+    store_q(splat(a.s)   * f32x4( s,  x,  y,  z) +
+            splat(a.v.x) * f32x4(-x,  s, -z,  y) +
+            splat(a.v.y) * f32x4(-y,  z,  s, -x) +
+            splat(a.v.z) * f32x4(-z, -y,  x,  s))
+}
+
+/// The dot product of two quaternions.
+#[inline]
+pub fn quat_dot(a: &Quat<f32>, b: &Quat<f32>) -> f32 {
+    sum(load_q(a) * load_q(b))
+}
+
+/// The quaternion scaled to a magnitude of one.
+#[inline]
+pub fn quat_normalize(a: &Quat<f32>) -> Quat<f32> {
+    let q = load_q(a);
+    store_q(q * splat(1.0 / sqrt(sum(q * q))))
+}
+
+/// The product of a matrix and a column vector.
+#[inline]
+pub fn mat4_mul_v(m: &Mat4<f32>, v: &Vec4<f32>) -> Vec4<f32> {
+    store_v(load_v(&m.x) * splat(v.x) +
+            load_v(&m.y) * splat(v.y) +
+            load_v(&m.z) * splat(v.z) +
+            load_v(&m.w) * splat(v.w))
+}
+
+/// The product of two matrices.
+#[inline]
+pub fn mat4_mul_m(a: &Mat4<f32>, b: &Mat4<f32>) -> Mat4<f32> {
+    Mat4 { x: mat4_mul_v(a, &b.x),
+           y: mat4_mul_v(a, &b.y),
+           z: mat4_mul_v(a, &b.z),
+           w: mat4_mul_v(a, &b.w) }
+}
+
+/// The inverse of a matrix, computed from its cofactors.
+///
+/// # Return value
+///
+/// The inverse, or `None` if the matrix is not invertible.
+pub fn mat4_invert(m: &Mat4<f32>) -> Option<Mat4<f32>> {
+    // 2 x 2 sub-determinants of the last two columns
+    let c00 = m.z.z * m.w.w - m.w.z * m.z.w;
+    let c02 = m.y.z * m.w.w - m.w.z * m.y.w;
+    let c03 = m.y.z * m.z.w - m.z.z * m.y.w;
+
+    let c04 = m.z.y * m.w.w - m.w.y * m.z.w;
+    let c06 = m.y.y * m.w.w - m.w.y * m.y.w;
+    let c07 = m.y.y * m.z.w - m.z.y * m.y.w;
+
+    let c08 = m.z.y * m.w.z - m.w.y * m.z.z;
+    let c10 = m.y.y * m.w.z - m.w.y * m.y.z;
+    let c11 = m.y.y * m.z.z - m.z.y * m.y.z;
+
+    let c12 = m.z.x * m.w.w - m.w.x * m.z.w;
+    let c14 = m.y.x * m.w.w - m.w.x * m.y.w;
+    let c15 = m.y.x * m.z.w - m.z.x * m.y.w;
+
+    let c16 = m.z.x * m.w.z - m.w.x * m.z.z;
+    let c18 = m.y.x * m.w.z - m.w.x * m.y.z;
+    let c19 = m.y.x * m.z.z - m.z.x * m.y.z;
+
+    let c20 = m.z.x * m.w.y - m.w.x * m.z.y;
+    let c22 = m.y.x * m.w.y - m.w.x * m.y.y;
+    let c23 = m.y.x * m.z.y - m.z.x * m.y.y;
+
+    let f0 = f32x4(c00, c00, c02, c03);
+    let f1 = f32x4(c04, c04, c06, c07);
+    let f2 = f32x4(c08, c08, c10, c11);
+    let f3 = f32x4(c12, c12, c14, c15);
+    let f4 = f32x4(c16, c16, c18, c19);
+    let f5 = f32x4(c20, c20, c22, c23);
+
+    let v0 = f32x4(m.y.x, m.x.x, m.x.x, m.x.x);
+    let v1 = f32x4(m.y.y, m.x.y, m.x.y, m.x.y);
+    let v2 = f32x4(m.y.z, m.x.z, m.x.z, m.x.z);
+    let v3 = f32x4(m.y.w, m.x.w, m.x.w, m.x.w);
+
+    let sign_a = f32x4(1.0, -1.0, 1.0, -1.0);
+    let sign_b = f32x4(-1.0, 1.0, -1.0, 1.0);
+
+    // the columns of the adjugate
+    let i0 = (v1 * f0 - v2 * f1 + v3 * f2) * sign_a;
+    let i1 = (v0 * f0 - v2 * f3 + v3 * f4) * sign_b;
+    let i2 = (v0 * f1 - v1 * f3 + v3 * f5) * sign_a;
+    let i3 = (v0 * f2 - v1 * f4 + v2 * f5) * sign_b;
+
+    let f32x4(r0, _, _, _) = i0;
+    let f32x4(r1, _, _, _) = i1;
+    let f32x4(r2, _, _, _) = i2;
+    let f32x4(r3, _, _, _) = i3;
+    let det = sum(load_v(&m.x) * f32x4(r0, r1, r2, r3));
+
+    if det.approx_eq(&0.0) {
+        None
+    } else {
+        let inv_det = splat(1.0 / det);
+        Some(Mat4 { x: store_v(i0 * inv_det),
+                    y: store_v(i1 * inv_det),
+                    z: store_v(i2 * inv_det),
+                    w: store_v(i3 * inv_det) })
+    }
+}
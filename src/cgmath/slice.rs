@@ -0,0 +1,112 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Zero-copy views of slices of math types as slices of scalars, and back.
+//!
+//! Vectors, points, matrices and quaternions are `#[repr(C)]` structs whose
+//! fields all have the same type, so they are laid out as their components,
+//! one after the other, with no padding:
+//!
+//! - `Vec2`, `Vec3`, `Vec4`, `Point2` and `Point3` as `x`, `y`, `z`, `w`.
+//! - `Quat` as `s`, `x`, `y`, `z`.
+//! - matrices as their columns, in column major order.
+//!
+//! This is the same layout that `Array::as_slice` relies on. This module
+//! extends it to whole slices, without copying, through associated functions
+//! on each of the types above, for example `Vec3::slice_as_scalars`. They
+//! are only provided for primitive scalar types, so that the layout of each
+//! type is always a whole number of its components.
+
+use std::cast::transmute;
+use std::vec;
+
+use matrix::{Mat2, Mat3, Mat4};
+use matrix::{Mat2x3, Mat2x4, Mat3x2, Mat3x4, Mat4x2, Mat4x3};
+use point::{Point2, Point3};
+use quaternion::Quat;
+use vector::{Vec2, Vec3, Vec4};
+
+#[inline]
+unsafe fn cast_slice<'a, A, B>(xs: &'a [A], len: uint) -> &'a [B] {
+    do vec::raw::buf_as_slice(vec::raw::to_ptr(xs) as *B, len) |ys| {
+        transmute::<&[B], &'a [B]>(ys)
+    }
+}
+
+#[inline]
+unsafe fn cast_mut_slice<'a, A, B>(xs: &'a mut [A], len: uint) -> &'a mut [B] {
+    do vec::raw::mut_buf_as_slice(vec::raw::to_mut_ptr(xs) as *mut B, len) |ys| {
+        transmute::<&mut [B], &'a mut [B]>(ys)
+    }
+}
+
+/// Generates the slice casts for a type that consists of `n` scalars.
+macro_rules! flat_slice(
+    ($Self:ident, $n:expr) => (
+        impl<S: Primitive> $Self<S> {
+            /// View a slice of values as a slice of their scalar components.
+            #[inline]
+            pub fn slice_as_scalars<'a>(xs: &'a [$Self<S>]) -> &'a [S] {
+                unsafe { cast_slice(xs, xs.len() * $n) }
+            }
+
+            /// View a mutable slice of values as a mutable slice of their
+            /// scalar components.
+            #[inline]
+            pub fn slice_as_mut_scalars<'a>(xs: &'a mut [$Self<S>]) -> &'a mut [S] {
+                let len = xs.len() * $n;
+                unsafe { cast_mut_slice(xs, len) }
+            }
+
+            /// View a slice of scalars as a slice of values, or `None` if the
+            /// length of `xs` is not a multiple of the number of components.
+            #[inline]
+            pub fn slice_from_scalars<'a>(xs: &'a [S]) -> Option<&'a [$Self<S>]> {
+                if xs.len() % $n != 0 { return None; }
+                Some(unsafe { cast_slice(xs, xs.len() / $n) })
+            }
+
+            /// View a mutable slice of scalars as a mutable slice of values, or
+            /// `None` if the length of `xs` is not a multiple of the number of
+            /// components.
+            #[inline]
+            pub fn slice_from_mut_scalars<'a>(xs: &'a mut [S]) -> Option<&'a mut [$Self<S>]> {
+                if xs.len() % $n != 0 { return None; }
+                let len = xs.len() / $n;
+                Some(unsafe { cast_mut_slice(xs, len) })
+            }
+        }
+    )
+)
+
+flat_slice!(Vec2, 2)
+flat_slice!(Vec3, 3)
+flat_slice!(Vec4, 4)
+
+flat_slice!(Point2, 2)
+flat_slice!(Point3, 3)
+
+flat_slice!(Quat, 4)
+
+flat_slice!(Mat2, 4)
+flat_slice!(Mat3, 9)
+flat_slice!(Mat4, 16)
+
+flat_slice!(Mat2x3, 6)
+flat_slice!(Mat2x4, 8)
+flat_slice!(Mat3x2, 6)
+flat_slice!(Mat3x4, 12)
+flat_slice!(Mat4x2, 8)
+flat_slice!(Mat4x3, 12)
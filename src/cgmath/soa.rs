@@ -0,0 +1,378 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Structure-of-arrays batches of vectors.
+//!
+//! `Vec3xN` stores many three-dimensional vectors or points as three separate
+//! arrays of components, so that an operation on the whole batch is a
+//! straight loop over contiguous scalars, instead of a loop over `Vec3`s that
+//! each go through `Array::build`. `Vec3x4` is the fixed-width equivalent for
+//! exactly four vectors, which is stored inline and does not allocate.
+
+use std::num::{one, zero};
+use std::vec;
+
+use array::{Array, build};
+use matrix::Mat4;
+use point::Point3;
+use vector::{Vector, Vec3, Vec4};
+
+/// A batch of three-dimensional vectors, stored as one array per component.
+///
+/// The component arrays always have the same length, so they can only be
+/// read from outside of this module.
+#[deriving(Clone, Eq)]
+pub struct Vec3xN<S> {
+    priv x: ~[S],
+    priv y: ~[S],
+    priv z: ~[S],
+}
+
+#[inline]
+fn zip_with<S>(a: &[S], b: &[S], f: &fn(&S, &S) -> S) -> ~[S] {
+    assert_eq!(a.len(), b.len());
+    a.iter().zip(b.iter()).map(|(a, b)| f(a, b)).collect()
+}
+
+impl<S: Primitive> Vec3xN<S> {
+    /// Create an empty batch.
+    #[inline]
+    pub fn new() -> Vec3xN<S> {
+        Vec3xN { x: ~[], y: ~[], z: ~[] }
+    }
+
+    /// Create an empty batch with room for `n` vectors.
+    #[inline]
+    pub fn with_capacity(n: uint) -> Vec3xN<S> {
+        Vec3xN { x: vec::with_capacity(n), y: vec::with_capacity(n), z: vec::with_capacity(n) }
+    }
+
+    /// Create a batch from a slice of vectors.
+    pub fn from_vecs(vs: &[Vec3<S>]) -> Vec3xN<S> {
+        Vec3xN { x: vs.iter().map(|v| v.x.clone()).collect(),
+                 y: vs.iter().map(|v| v.y.clone()).collect(),
+                 z: vs.iter().map(|v| v.z.clone()).collect() }
+    }
+
+    /// Create a batch from a slice of points, holding their displacements
+    /// from the origin.
+    pub fn from_points(ps: &[Point3<S>]) -> Vec3xN<S> {
+        Vec3xN { x: ps.iter().map(|p| p.x.clone()).collect(),
+                 y: ps.iter().map(|p| p.y.clone()).collect(),
+                 z: ps.iter().map(|p| p.z.clone()).collect() }
+    }
+
+    /// Convert the batch back to an array of vectors.
+    pub fn to_vecs(&self) -> ~[Vec3<S>] {
+        do vec::from_fn(self.len()) |i| { self.get(i) }
+    }
+
+    /// Convert the batch back to an array of points.
+    pub fn to_points(&self) -> ~[Point3<S>] {
+        do vec::from_fn(self.len()) |i| { Point3::from_vec(&self.get(i)) }
+    }
+
+    /// The number of vectors in the batch.
+    #[inline]
+    pub fn len(&self) -> uint { self.x.len() }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.x.is_empty() }
+
+    /// The `x` components of the vectors.
+    #[inline]
+    pub fn xs<'a>(&'a self) -> &'a [S] { self.x.as_slice() }
+
+    /// The `y` components of the vectors.
+    #[inline]
+    pub fn ys<'a>(&'a self) -> &'a [S] { self.y.as_slice() }
+
+    /// The `z` components of the vectors.
+    #[inline]
+    pub fn zs<'a>(&'a self) -> &'a [S] { self.z.as_slice() }
+
+    /// The vector at index `i`.
+    #[inline]
+    pub fn get(&self, i: uint) -> Vec3<S> {
+        Vec3::new(self.x[i].clone(), self.y[i].clone(), self.z[i].clone())
+    }
+
+    /// Replace the vector at index `i`.
+    #[inline]
+    pub fn set(&mut self, i: uint, v: &Vec3<S>) {
+        self.x[i] = v.x.clone();
+        self.y[i] = v.y.clone();
+        self.z[i] = v.z.clone();
+    }
+
+    /// Append a vector to the end of the batch.
+    #[inline]
+    pub fn push(&mut self, v: &Vec3<S>) {
+        self.x.push(v.x.clone());
+        self.y.push(v.y.clone());
+        self.z.push(v.z.clone());
+    }
+
+    /// The element-wise sum of two batches of the same length.
+    pub fn add_v(&self, other: &Vec3xN<S>) -> Vec3xN<S> {
+        Vec3xN { x: zip_with(self.x, other.x, |a, b| *a + *b),
+                 y: zip_with(self.y, other.y, |a, b| *a + *b),
+                 z: zip_with(self.z, other.z, |a, b| *a + *b) }
+    }
+
+    /// The element-wise difference of two batches of the same length.
+    pub fn sub_v(&self, other: &Vec3xN<S>) -> Vec3xN<S> {
+        Vec3xN { x: zip_with(self.x, other.x, |a, b| *a - *b),
+                 y: zip_with(self.y, other.y, |a, b| *a - *b),
+                 z: zip_with(self.z, other.z, |a, b| *a - *b) }
+    }
+
+    /// Every vector multiplied by a scalar.
+    pub fn mul_s(&self, s: S) -> Vec3xN<S> {
+        Vec3xN { x: self.x.iter().map(|a| *a * s).collect(),
+                 y: self.y.iter().map(|a| *a * s).collect(),
+                 z: self.z.iter().map(|a| *a * s).collect() }
+    }
+
+    /// The dot products of the corresponding vectors of two batches of the
+    /// same length.
+    pub fn dot(&self, other: &Vec3xN<S>) -> ~[S] {
+        assert_eq!(self.len(), other.len());
+        do vec::from_fn(self.len()) |i| {
+            self.x[i] * other.x[i] + self.y[i] * other.y[i] + self.z[i] * other.z[i]
+        }
+    }
+
+    /// The cross products of the corresponding vectors of two batches of the
+    /// same length.
+    pub fn cross(&self, other: &Vec3xN<S>) -> Vec3xN<S> {
+        assert_eq!(self.len(), other.len());
+        let n = self.len();
+        Vec3xN {
+            x: do vec::from_fn(n) |i| { (self.y[i] * other.z[i]) - (self.z[i] * other.y[i]) },
+            y: do vec::from_fn(n) |i| { (self.z[i] * other.x[i]) - (self.x[i] * other.z[i]) },
+            z: do vec::from_fn(n) |i| { (self.x[i] * other.y[i]) - (self.y[i] * other.x[i]) },
+        }
+    }
+
+    /// The squared lengths of the vectors.
+    pub fn length2(&self) -> ~[S] {
+        self.dot(self)
+    }
+
+    /// Transform every vector by an affine matrix, treating them as points,
+    /// with a `w` component of one. The result is not divided by `w`.
+    pub fn transform_points(&self, m: &Mat4<S>) -> Vec3xN<S> {
+        self.transform(m, one())
+    }
+
+    /// Transform every vector by a matrix, treating them as directions, with
+    /// a `w` component of zero.
+    pub fn transform_vectors(&self, m: &Mat4<S>) -> Vec3xN<S> {
+        self.transform(m, zero())
+    }
+
+    fn transform(&self, m: &Mat4<S>, w: S) -> Vec3xN<S> {
+        // hoist the matrix out of the loop
+        let (xx, xy, xz) = (m.x.x.clone(), m.x.y.clone(), m.x.z.clone());
+        let (yx, yy, yz) = (m.y.x.clone(), m.y.y.clone(), m.y.z.clone());
+        let (zx, zy, zz) = (m.z.x.clone(), m.z.y.clone(), m.z.z.clone());
+        let (wx, wy, wz) = (m.w.x * w, m.w.y * w, m.w.z * w);
+
+        let n = self.len();
+        let mut result = Vec3xN::with_capacity(n);
+        for i in range(0u, n) {
+            let (x, y, z) = (self.x[i].clone(), self.y[i].clone(), self.z[i].clone());
+            result.x.push(xx * x + yx * y + zx * z + wx);
+            result.y.push(xy * x + yy * y + zy * z + wy);
+            result.z.push(xz * x + yz * y + zz * z + wz);
+        }
+        result
+    }
+}
+
+impl<S: Float> Vec3xN<S> {
+    /// The lengths of the vectors.
+    pub fn length(&self) -> ~[S] {
+        self.length2().iter().map(|l2| l2.sqrt()).collect()
+    }
+
+    /// Every vector scaled to a length of one.
+    pub fn normalize(&self) -> Vec3xN<S> {
+        let n = self.len();
+        let mut result = Vec3xN::with_capacity(n);
+        for i in range(0u, n) {
+            let (x, y, z) = (self.x[i].clone(), self.y[i].clone(), self.z[i].clone());
+            let inv = one::<S>() / (x * x + y * y + z * z).sqrt();
+            result.x.push(x * inv);
+            result.y.push(y * inv);
+            result.z.push(z * inv);
+        }
+        result
+    }
+}
+
+/// A batch of exactly four three-dimensional vectors, stored as one `Vec4`
+/// per component, so that every operation works on four vectors at once.
+#[deriving(Clone, Eq)]
+pub struct Vec3x4<S> {
+    x: Vec4<S>,
+    y: Vec4<S>,
+    z: Vec4<S>,
+}
+
+impl<S: Primitive> Vec3x4<S> {
+    #[inline]
+    pub fn new(x: Vec4<S>, y: Vec4<S>, z: Vec4<S>) -> Vec3x4<S> {
+        Vec3x4 { x: x, y: y, z: z }
+    }
+
+    /// Create a batch holding four copies of the same vector.
+    #[inline]
+    pub fn splat(v: &Vec3<S>) -> Vec3x4<S> {
+        Vec3x4 { x: build(|_| v.x.clone()),
+                 y: build(|_| v.y.clone()),
+                 z: build(|_| v.z.clone()) }
+    }
+
+    /// Create a batch from a slice of exactly four vectors.
+    #[inline]
+    pub fn from_vecs(vs: &[Vec3<S>]) -> Vec3x4<S> {
+        assert_eq!(vs.len(), 4);
+        Vec3x4 { x: build(|i| vs[i].x.clone()),
+                 y: build(|i| vs[i].y.clone()),
+                 z: build(|i| vs[i].z.clone()) }
+    }
+
+    /// Create a batch from a slice of exactly four points, holding their
+    /// displacements from the origin.
+    #[inline]
+    pub fn from_points(ps: &[Point3<S>]) -> Vec3x4<S> {
+        assert_eq!(ps.len(), 4);
+        Vec3x4 { x: build(|i| ps[i].x.clone()),
+                 y: build(|i| ps[i].y.clone()),
+                 z: build(|i| ps[i].z.clone()) }
+    }
+
+    /// Convert the batch back to an array of vectors.
+    pub fn to_vecs(&self) -> ~[Vec3<S>] {
+        do vec::from_fn(4) |i| { self.get(i) }
+    }
+
+    /// Convert the batch back to an array of points.
+    pub fn to_points(&self) -> ~[Point3<S>] {
+        do vec::from_fn(4) |i| { Point3::from_vec(&self.get(i)) }
+    }
+
+    /// The vector at index `i`.
+    #[inline]
+    pub fn get(&self, i: uint) -> Vec3<S> {
+        Vec3::new(self.x.i(i).clone(), self.y.i(i).clone(), self.z.i(i).clone())
+    }
+
+    /// Replace the vector at index `i`.
+    #[inline]
+    pub fn set(&mut self, i: uint, v: &Vec3<S>) {
+        *self.x.mut_i(i) = v.x.clone();
+        *self.y.mut_i(i) = v.y.clone();
+        *self.z.mut_i(i) = v.z.clone();
+    }
+
+    /// The element-wise sum of two batches.
+    #[inline]
+    pub fn add_v(&self, other: &Vec3x4<S>) -> Vec3x4<S> {
+        Vec3x4 { x: self.x.add_v(&other.x),
+                 y: self.y.add_v(&other.y),
+                 z: self.z.add_v(&other.z) }
+    }
+
+    /// The element-wise difference of two batches.
+    #[inline]
+    pub fn sub_v(&self, other: &Vec3x4<S>) -> Vec3x4<S> {
+        Vec3x4 { x: self.x.sub_v(&other.x),
+                 y: self.y.sub_v(&other.y),
+                 z: self.z.sub_v(&other.z) }
+    }
+
+    /// Every vector multiplied by a scalar.
+    #[inline]
+    pub fn mul_s(&self, s: S) -> Vec3x4<S> {
+        Vec3x4 { x: self.x.mul_s(s.clone()),
+                 y: self.y.mul_s(s.clone()),
+                 z: self.z.mul_s(s) }
+    }
+
+    /// The dot products of the corresponding vectors of two batches.
+    #[inline]
+    pub fn dot(&self, other: &Vec3x4<S>) -> Vec4<S> {
+        self.x.mul_v(&other.x)
+            .add_v(&self.y.mul_v(&other.y))
+            .add_v(&self.z.mul_v(&other.z))
+    }
+
+    /// The cross products of the corresponding vectors of two batches.
+    #[inline]
+    pub fn cross(&self, other: &Vec3x4<S>) -> Vec3x4<S> {
+        Vec3x4 { x: self.y.mul_v(&other.z).sub_v(&self.z.mul_v(&other.y)),
+                 y: self.z.mul_v(&other.x).sub_v(&self.x.mul_v(&other.z)),
+                 z: self.x.mul_v(&other.y).sub_v(&self.y.mul_v(&other.x)) }
+    }
+
+    /// The squared lengths of the vectors.
+    #[inline]
+    pub fn length2(&self) -> Vec4<S> {
+        self.dot(self)
+    }
+
+    /// Transform every vector by an affine matrix, treating them as points,
+    /// with a `w` component of one. The result is not divided by `w`.
+    #[inline]
+    pub fn transform_points(&self, m: &Mat4<S>) -> Vec3x4<S> {
+        self.transform(m, one())
+    }
+
+    /// Transform every vector by a matrix, treating them as directions, with
+    /// a `w` component of zero.
+    #[inline]
+    pub fn transform_vectors(&self, m: &Mat4<S>) -> Vec3x4<S> {
+        self.transform(m, zero())
+    }
+
+    fn transform(&self, m: &Mat4<S>, w: S) -> Vec3x4<S> {
+        Vec3x4 {
+            x: build(|i| m.x.x * *self.x.i(i) + m.y.x * *self.y.i(i) + m.z.x * *self.z.i(i) + m.w.x * w),
+            y: build(|i| m.x.y * *self.x.i(i) + m.y.y * *self.y.i(i) + m.z.y * *self.z.i(i) + m.w.y * w),
+            z: build(|i| m.x.z * *self.x.i(i) + m.y.z * *self.y.i(i) + m.z.z * *self.z.i(i) + m.w.z * w),
+        }
+    }
+}
+
+impl<S: Float> Vec3x4<S> {
+    /// The lengths of the vectors.
+    #[inline]
+    pub fn length(&self) -> Vec4<S> {
+        let l2 = self.length2();
+        build(|i| l2.i(i).sqrt())
+    }
+
+    /// Every vector scaled to a length of one.
+    #[inline]
+    pub fn normalize(&self) -> Vec3x4<S> {
+        let l = self.length();
+        Vec3x4 { x: self.x.div_v(&l),
+                 y: self.y.div_v(&l),
+                 z: self.z.div_v(&l) }
+    }
+}
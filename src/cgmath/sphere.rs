@@ -15,6 +15,10 @@
 
 //! Bounding sphere
 
+use std::from_str::{FromStr, from_str};
+
+use approx::{RelativeEq, UlpsEq, default_epsilon};
+use format::{ToStrDigits, format_list, split_list};
 use point::Point3;
 
 #[deriving(Clone, Eq)]
@@ -22,3 +26,7 @@ pub struct Sphere<S> {
     center: Point3<S>,
     radius: S,
 }
+
+approx_eq_fields!(impl<S> Sphere<S> { center, radius })
+
+format_fields!(impl<S> Sphere { center, radius })
@@ -13,20 +13,26 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::from_str::FromStr;
 use std::num::{Zero, zero, One, one, sqrt};
 
 use angle::{Rad, atan2, acos};
+use approx::{RelativeEq, UlpsEq, default_epsilon};
 use array::{Array, build};
+use format::{ToStrDigits, format_list, parse_list};
 
 /// A 2-dimensional vector.
+#[repr(C)]
 #[deriving(Eq, Clone, Zero)]
 pub struct Vec2<S> { x: S, y: S }
 
 /// A 3-dimensional vector.
+#[repr(C)]
 #[deriving(Eq, Clone, Zero)]
 pub struct Vec3<S> { x: S, y: S, z: S }
 
 /// A 4-dimensional vector.
+#[repr(C)]
 #[deriving(Eq, Clone, Zero)]
 pub struct Vec4<S> { x: S, y: S, z: S, w: S }
 
@@ -91,6 +97,10 @@ approx_eq!(impl<S> Vec2<S>)
 approx_eq!(impl<S> Vec3<S>)
 approx_eq!(impl<S> Vec4<S>)
 
+format_array!(impl<S> Vec2<S>, 2)
+format_array!(impl<S> Vec3<S>, 3)
+format_array!(impl<S> Vec4<S>, 4)
+
 /// A trait that specifies a range of numeric operations for vectors. Not all
 /// of these make sense from a linear algebra point of view, but are included
 /// for pragmatic reasons.
@@ -281,21 +291,3 @@ impl<S: Float> EuclideanVector<S, [S, ..4]> for Vec4<S> {
         acos(self.dot(other) / (self.length() * other.length()))
     }
 }
-
-impl<S> ToStr for Vec2<S> {
-    fn to_str(&self) -> ~str {
-        fmt!("[%?, %?]", self.x, self.y)
-    }
-}
-
-impl<S> ToStr for Vec3<S> {
-    fn to_str(&self) -> ~str {
-        fmt!("[%?, %?, %?]", self.x, self.y, self.z)
-    }
-}
-
-impl<S> ToStr for Vec4<S> {
-    fn to_str(&self) -> ~str {
-        fmt!("[%?, %?, %?, %?]", self.x, self.y, self.z, self.w)
-    }
-}
@@ -0,0 +1,83 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cast::transmute;
+
+use cgmath::angle::*;
+use cgmath::approx::*;
+use cgmath::matrix::*;
+use cgmath::plane::*;
+use cgmath::point::*;
+use cgmath::quaternion::*;
+use cgmath::sphere::*;
+use cgmath::vector::*;
+
+#[test]
+fn test_default_epsilon() {
+    // each scalar type has a tolerance that matches its precision
+    let eps32: f32 = default_epsilon();
+    let eps64: f64 = default_epsilon();
+    assert!(1.0f32 + eps32 != 1.0 && 1.0f32 + 0.5 * eps32 == 1.0);
+    assert!(1.0f64 + eps64 != 1.0 && 1.0f64 + 0.5 * eps64 == 1.0);
+    assert!(eps64 < eps32 as f64);
+
+    let eps = 1.0e-3f64;
+    assert!(Vec3::new(1.0f64, 2.0, 3.0).approx_eq_eps(&Vec3::new(1.0, 2.0, 3.0 + 0.5 * eps), &eps));
+    assert!(!Vec3::new(1.0f64, 2.0, 3.0).approx_eq_eps(&Vec3::new(1.0, 2.0, 3.0 + 2.0 * eps), &eps));
+}
+
+#[test]
+fn test_relative_eq() {
+    // large values differ by much more than the absolute tolerance
+    assert!(1.0e10f64.relative_eq(&(1.0e10 + 1.0), &1.0e-6, &1.0e-9));
+    assert!(!1.0e10f64.relative_eq(&(1.0e10 + 100.0), &1.0e-6, &1.0e-9));
+    // small values are compared with the absolute tolerance
+    assert!(0.0f64.relative_eq(&1.0e-7, &1.0e-6, &1.0e-9));
+    assert!(!1.0e-3f32.relative_eq(&1.1e-3, &1.0e-6, &1.0e-3));
+
+    assert_relative_eq!(Vec3::new(1.0e8f64, 2.0, -3.0e8), Vec3::new(1.0e8 + 1.0, 2.0, -3.0e8 - 1.0), 1.0e-6, 1.0e-7);
+    assert_relative_eq!(Point3::new(1.0e8f64, 2.0, -3.0e8), Point3::new(1.0e8 + 1.0, 2.0, -3.0e8), 1.0e-6, 1.0e-7);
+    assert_relative_eq!(Mat2::new(1.0e8f64, 0.0, 0.0, 1.0), Mat2::new(1.0e8 + 1.0, 0.0, 0.0, 1.0), 1.0e-6, 1.0e-7);
+    assert_relative_eq!(rad(1.0e8f64), rad(1.0e8 + 1.0), 1.0e-6, 1.0e-7);
+    assert!(!Vec2::new(1.0f64, 1.0e8).relative_eq(&Vec2::new(1.1, 1.0e8), &1.0e-6, &1.0e-7));
+}
+
+#[test]
+fn test_ulps_eq() {
+    let one = 1.0f32;
+    let next: f32 = unsafe { transmute(transmute::<f32, i32>(one) + 1) };
+    assert!(one.ulps_eq(&next, 1));
+    assert!(!one.ulps_eq(&next, 0));
+    assert!(0.0f64.ulps_eq(&-0.0, 0));
+    assert!(!1.0f64.ulps_eq(&-1.0, 1000));
+    assert!(!(0.0f64 / 0.0).ulps_eq(&(0.0 / 0.0), 1000));
+
+    assert_ulps_eq!(0.1f64 + 0.2, 0.3, 1);
+    assert_ulps_eq!(Quat::new(0.1f64 + 0.2, 0.0, 0.0, 1.0), Quat::new(0.3, 0.0, 0.0, 1.0), 1);
+    assert_ulps_eq!(Mat3::<f64>::identity(), Mat3::identity(), 0);
+    assert!(!Vec2::new(0.1f64 + 0.2, 0.0).ulps_eq(&Vec2::new(0.3, 0.0), 0));
+}
+
+#[test]
+fn test_fields() {
+    let a = Sphere { center: Point3::new(1.0f64, 2.0, 3.0), radius: 1.0e8 };
+    let b = Sphere { center: Point3::new(1.0f64, 2.0, 3.0), radius: 1.0e8 + 1.0 };
+    assert!(!a.approx_eq(&b));
+    assert_relative_eq!(a, b, 1.0e-6, 1.0e-7);
+
+    let p = Plane::from_abcd(0.0f64, 1.0, 0.0, 2.0);
+    assert_approx_eq!(p, Plane::from_abcd(0.0, 1.0, 0.0, 2.0 + 1.0e-7));
+    assert!(p.approx_eq_eps(&Plane::from_abcd(0.0, 1.0, 0.0, 2.1), &0.2));
+}
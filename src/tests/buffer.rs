@@ -0,0 +1,154 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cast::transmute;
+
+use cgmath::buffer::*;
+use cgmath::matrix::*;
+use cgmath::vector::*;
+
+fn f32_at(bytes: &[u8], offset: uint) -> f32 {
+    let b = [bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]];
+    unsafe { transmute(b) }
+}
+
+#[test]
+fn test_sizes() {
+    let v2 = Vec2::new(0.0f32, 0.0);
+    let v3 = Vec3::new(0.0f32, 0.0, 0.0);
+    let m2: Mat2<f32> = Mat2::identity();
+    let m3: Mat3<f32> = Mat3::identity();
+    let m4: Mat4<f32> = Mat4::identity();
+
+    assert_eq!(v2.alignment(Std140), 8);
+    assert_eq!(v3.alignment(Std140), 16);
+    assert_eq!(v3.size(Std140), 12);
+    assert_eq!(Vec3::new(0.0f64, 0.0, 0.0).alignment(Std430), 32);
+
+    assert_eq!(m2.size(Std140), 32);
+    assert_eq!(m2.size(Std430), 16);
+    assert_eq!(m3.size(Std140), 48);
+    assert_eq!(m3.size(Std430), 48);
+    assert_eq!(m4.size(Std140), 64);
+    assert_eq!(m4.size(Std430), 64);
+}
+
+#[test]
+fn test_std140_offsets() {
+    let mut w = LayoutWriter::new(Std140);
+    assert_eq!(w.write(&1.0f32), 0);
+    assert_eq!(w.write(&Vec3::new(2.0f32, 3.0, 4.0)), 16);
+    // a scalar may follow a vec3 directly
+    assert_eq!(w.write(&5.0f32), 28);
+    assert_eq!(w.write(&Vec2::new(6.0f32, 7.0)), 32);
+    let m3: Mat3<f32> = Mat3::identity();
+    assert_eq!(w.write(&m3), 48);
+    let m4: Mat4<f32> = Mat4::identity();
+    assert_eq!(w.write(&m4), 96);
+    assert_eq!(w.offset(), 160);
+
+    let bytes = w.unwrap();
+    assert_eq!(bytes.len(), 160);
+    assert_eq!(f32_at(bytes, 0), 1.0);
+    assert!(bytes.slice(4, 16).iter().all(|b| *b == 0));
+    assert_eq!(f32_at(bytes, 16), 2.0);
+    assert_eq!(f32_at(bytes, 24), 4.0);
+    assert_eq!(f32_at(bytes, 28), 5.0);
+    assert_eq!(f32_at(bytes, 36), 7.0);
+}
+
+#[test]
+fn test_mat3_columns() {
+    let m = Mat3::new(1.0f32, 2.0, 3.0,
+                      4.0f32, 5.0, 6.0,
+                      7.0f32, 8.0, 9.0);
+    for layout in (&[Std140, Std430]).iter() {
+        let mut w = LayoutWriter::new(*layout);
+        w.write(&m);
+        let bytes = w.unwrap();
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(f32_at(bytes, 0), 1.0);
+        assert_eq!(f32_at(bytes, 8), 3.0);
+        assert_eq!(f32_at(bytes, 12), 0.0);
+        assert_eq!(f32_at(bytes, 16), 4.0);
+        assert_eq!(f32_at(bytes, 32), 7.0);
+        assert_eq!(f32_at(bytes, 40), 9.0);
+    }
+}
+
+#[test]
+fn test_scalar_array() {
+    let xs = &[1.0f32, 2.0, 3.0];
+
+    let mut w = LayoutWriter::new(Std140);
+    w.write(&0.0f32);
+    assert_eq!(w.write_array(xs), 16);
+    assert_eq!(w.offset(), 64);
+    let bytes = w.unwrap();
+    assert_eq!(f32_at(bytes, 32), 2.0);
+    assert_eq!(f32_at(bytes, 48), 3.0);
+
+    let mut w = LayoutWriter::new(Std430);
+    w.write(&0.0f32);
+    assert_eq!(w.write_array(xs), 4);
+    assert_eq!(w.offset(), 16);
+    let bytes = w.unwrap();
+    assert_eq!(f32_at(bytes, 8), 2.0);
+    assert_eq!(f32_at(bytes, 12), 3.0);
+}
+
+#[test]
+fn test_vec3_array() {
+    let vs = &[Vec3::new(1.0f32, 2.0, 3.0), Vec3::new(4.0f32, 5.0, 6.0)];
+    for layout in (&[Std140, Std430]).iter() {
+        let mut w = LayoutWriter::new(*layout);
+        assert_eq!(w.write_array(vs), 0);
+        assert_eq!(w.offset(), 32);
+        let bytes = w.unwrap();
+        assert_eq!(f32_at(bytes, 12), 0.0);
+        assert_eq!(f32_at(bytes, 16), 4.0);
+        assert_eq!(f32_at(bytes, 24), 6.0);
+    }
+}
+
+#[test]
+fn test_matrix_array() {
+    let m3: Mat3<f32> = Mat3::identity();
+    let m4: Mat4<f32> = Mat4::identity();
+    let m2: Mat2<f32> = Mat2::identity();
+
+    let mut w = LayoutWriter::new(Std140);
+    w.write(&1.0f32);
+    assert_eq!(w.write_array(&[m3, m3]), 16);
+    assert_eq!(w.write_array(&[m4, m4, m4]), 112);
+    assert_eq!(w.write_array(&[m2, m2]), 304);
+    assert_eq!(w.offset(), 368);
+
+    let mut w = LayoutWriter::new(Std430);
+    w.write(&1.0f32);
+    assert_eq!(w.write_array(&[m3, m3]), 16);
+    assert_eq!(w.write_array(&[m4, m4, m4]), 112);
+    assert_eq!(w.write_array(&[m2, m2]), 304);
+    assert_eq!(w.offset(), 336);
+}
+
+#[test]
+fn test_empty_array() {
+    let mut w = LayoutWriter::new(Std140);
+    w.write(&1.0f32);
+    let empty: &[Vec4<f32>] = &[];
+    assert_eq!(w.write_array(empty), 4);
+    assert_eq!(w.offset(), 4);
+}
@@ -0,0 +1,70 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use cgmath::angle::*;
+use cgmath::cascade::*;
+use cgmath::matrix::*;
+use cgmath::projection::*;
+use cgmath::vector::*;
+
+fn assert_splits(actual: ~[f64], expected: ~[f64]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert_approx_eq!(*a, *e);
+    }
+}
+
+#[test]
+fn test_split_distances() {
+    assert_splits(split_distances(1.0f64, 10.0, 3, Uniform), ~[1.0, 4.0, 7.0, 10.0]);
+    assert_splits(split_distances(1.0f64, 1000.0, 3, Logarithmic), ~[1.0, 10.0, 100.0, 1000.0]);
+    assert_splits(split_distances(1.0f64, 1000.0, 3, Practical(0.0)), ~[1.0, 334.0, 667.0, 1000.0]);
+    assert_splits(split_distances(1.0f64, 1000.0, 3, Practical(0.5)), ~[1.0, 172.0, 383.5, 1000.0]);
+}
+
+fn camera_to_world(yaw: f64, x: f64, z: f64) -> Mat4<f64> {
+    let mut m = Mat3::from_angle_y(deg(yaw)).to_mat4();
+    m.w = Vec4::new(x, 0.0, z, 1.0);
+    m
+}
+
+#[test]
+fn test_cascades() {
+    let camera = PerspectiveFov::new(deg(60.0f64), 1.5, 0.5, 100.0).unwrap();
+    let light = Vec3::new(0.3f64, -1.0, 0.2);
+    let resolution = 1024;
+
+    let a = cascades(&camera, &camera_to_world(0.0, 0.0, 0.0), &light, 4, Practical(0.75), resolution);
+    let b = cascades(&camera, &camera_to_world(37.0, 12.3, -4.56), &light, 4, Practical(0.75), resolution);
+    assert_eq!(a.len(), 4);
+    assert_approx_eq!(a[0].near, 0.5);
+    assert_approx_eq!(a[3].far, 100.0);
+
+    for i in range(0u, 4) {
+        assert_approx_eq!(a[i].near, b[i].near);
+        assert_approx_eq!(a[i].far, b[i].far);
+        if i > 0 { assert_approx_eq!(a[i].near, a[i - 1].far); }
+
+        // the size of each projection does not depend on the camera transform
+        let width = a[i].proj.right - a[i].proj.left;
+        assert_approx_eq!(width, b[i].proj.right - b[i].proj.left);
+        assert_approx_eq!(width, a[i].proj.top - a[i].proj.bottom);
+
+        // and its position is snapped to whole texels
+        let texel = width / (resolution as f64);
+        let offset = (b[i].proj.left + width * 0.5) / texel;
+        assert_approx_eq!(offset, offset.round());
+    }
+}
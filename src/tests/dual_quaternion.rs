@@ -0,0 +1,92 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use cgmath::angle::*;
+use cgmath::dual_quaternion::*;
+use cgmath::matrix::*;
+use cgmath::point::*;
+use cgmath::quaternion::*;
+use cgmath::vector::*;
+
+fn rot_z(degrees: f64) -> Quat<f64> {
+    Quat::from_axis_angle(&Vec3::unit_z(), deg(degrees))
+}
+
+#[test]
+fn test_transform_point() {
+    let disp = Vec3::new(1.0f64, 2.0, 3.0);
+    let dq = DualQuat::from_rotation_translation(&rot_z(90.0), &disp);
+
+    assert_approx_eq!(dq.rotation(), rot_z(90.0));
+    assert_approx_eq!(dq.translation(), disp);
+    assert_approx_eq!(dq.transform_point(&Point3::new(1.0, 0.0, 0.0)), Point3::new(1.0, 3.0, 3.0));
+    assert_approx_eq!(dq.transform_vector(&Vec3::unit_x()), Vec3::unit_y());
+
+    // the conjugate is the inverse transform
+    assert_approx_eq!(dq.mul_d(&dq.conjugate()), DualQuat::identity());
+}
+
+#[test]
+fn test_mul_d() {
+    let a = DualQuat::from_rotation_translation(&rot_z(90.0), &Vec3::new(1.0f64, 0.0, 0.0));
+    let b = DualQuat::from_rotation_translation(&rot_z(-30.0), &Vec3::new(0.0f64, 2.0, 0.0));
+    let p = Point3::new(1.0f64, 2.0, 3.0);
+
+    assert_approx_eq!(a.mul_d(&b).transform_point(&p), a.transform_point(&b.transform_point(&p)));
+    assert_approx_eq!(a.mul_d(&b).to_mat4(), a.to_mat4().mul_m(&b.to_mat4()));
+}
+
+#[test]
+fn test_mat4() {
+    let dq = DualQuat::from_rotation_translation(&Quat::from_axis_angle(&Vec3::new(0.0f64, 0.6, 0.8), deg(70.0f64)),
+                                                 &Vec3::new(-1.0f64, 2.0, 0.5));
+    let mat = dq.to_mat4();
+    let p = dq.transform_point(&Point3::new(1.0, 2.0, 3.0));
+
+    assert_approx_eq!(mat.mul_v(&Vec4::new(1.0, 2.0, 3.0, 1.0)), Vec4::new(p.x, p.y, p.z, 1.0));
+    assert_approx_eq!(DualQuat::from_mat4(&mat), dq);
+}
+
+#[test]
+fn test_normalize() {
+    let dq = DualQuat::from_rotation_translation(&rot_z(40.0), &Vec3::new(1.0f64, 2.0, 3.0));
+    assert_approx_eq!(dq.mul_s(3.0).normalize(), dq);
+}
+
+#[test]
+fn test_sclerp() {
+    let a = DualQuat::from_translation(&Vec3::new(0.0f64, 0.0, 0.0));
+    let b = DualQuat::from_translation(&Vec3::new(2.0f64, 4.0, 0.0));
+    assert_approx_eq!(a.sclerp(&b, 0.5), DualQuat::from_translation(&Vec3::new(1.0, 2.0, 0.0)));
+
+    // a screw motion around the z axis
+    let a = DualQuat::from_rotation_translation(&rot_z(0.0), &Vec3::new(0.0f64, 0.0, 0.0));
+    let b = DualQuat::from_rotation_translation(&rot_z(90.0), &Vec3::new(0.0f64, 0.0, 4.0));
+    let mid = DualQuat::from_rotation_translation(&rot_z(45.0), &Vec3::new(0.0f64, 0.0, 2.0));
+    assert_approx_eq!(a.sclerp(&b, 0.0), a);
+    assert_approx_eq!(a.sclerp(&b, 0.5), mid);
+    assert_approx_eq!(a.sclerp(&b, 1.0), b);
+    assert_approx_eq!(a.sclerp(&-b, 0.5), mid);
+}
+
+#[test]
+fn test_blend() {
+    let a = DualQuat::from_rotation_translation(&rot_z(0.0), &Vec3::new(0.0f64, 0.0, 0.0));
+    let b = DualQuat::from_rotation_translation(&rot_z(90.0), &Vec3::new(0.0f64, 0.0, 4.0));
+    let blended = DualQuat::blend(&[a, -b], &[0.5, 0.5]);
+
+    assert_approx_eq!(blended, a.sclerp(&b, 0.5));
+    assert_approx_eq!(DualQuat::blend(&[b], &[1.0]), b);
+}
@@ -0,0 +1,172 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::from_str::from_str;
+
+use cgmath::aabb::*;
+use cgmath::angle::*;
+use cgmath::dual_quaternion::*;
+use cgmath::format::*;
+use cgmath::matrix::*;
+use cgmath::plane::*;
+use cgmath::point::*;
+use cgmath::quaternion::*;
+use cgmath::ray::*;
+use cgmath::rotation::*;
+use cgmath::sphere::*;
+use cgmath::vector::*;
+
+#[test]
+fn test_to_str() {
+    assert_eq!(Vec3::new(1.0f64, 2.5, -3.0).to_str(), ~"[1, 2.5, -3]");
+    assert_eq!(Point2::new(0.5f64, 4.0).to_str(), ~"[0.5, 4]");
+    assert_eq!(Quat::new(1.0f64, 0.0, 2.0, 0.0).to_str(), ~"[1, 0, 2, 0]");
+    assert_eq!(Plane::from_abcd(0.0f64, 1.0, 0.0, 2.0).to_str(), ~"[0, 1, 0, 2]");
+    assert_eq!(rad(1.5f64).to_str(), ~"1.5 rad");
+    assert_eq!(deg(90.0f64).to_str(), ~"90°");
+    assert_eq!(DualQuat::new(Quat::new(1.0f64, 0.0, 0.0, 0.0), Quat::new(0.0, 1.0, 2.0, 3.0)).to_str(),
+               ~"[[1, 0, 0, 0], [0, 1, 2, 3]]");
+}
+
+#[test]
+fn test_matrix_to_str() {
+    // matrices are written row by row
+    assert_eq!(Mat2::new(1.0f64, 2.0, 3.0, 4.0).to_str(), ~"[[1, 3], [2, 4]]");
+    assert_eq!(Mat3::<f64>::identity().to_str(), ~"[[1, 0, 0], [0, 1, 0], [0, 0, 1]]");
+    assert_eq!(Mat2x3::new(1.0f64, 2.0, 3.0, 4.0, 5.0, 6.0).to_str(), ~"[[1, 4], [2, 5], [3, 6]]");
+}
+
+#[test]
+fn test_to_str_digits() {
+    assert_eq!(Vec2::new(1.0f64, 1.0 / 3.0).to_str_digits(2), ~"[1.00, 0.33]");
+    assert_eq!(Vec2::new(0.3f32, -2.0).to_str_digits(1), ~"[0.3, -2.0]");
+    assert_eq!(Mat2::new(1.0f64, 0.0, 0.5, 1.0).to_str_digits(1), ~"[[1.0, 0.5], [0.0, 1.0]]");
+    assert_eq!(deg(45.0f64).to_str_digits(3), ~"45.000°");
+}
+
+#[test]
+fn test_from_str() {
+    assert_eq!(from_str::<Vec3<f64>>("[1, 2, 3]"), Some(Vec3::new(1.0, 2.0, 3.0)));
+    assert_eq!(from_str::<Vec3<f64>>("  [ 1.5,2 , -3e2 ] "), Some(Vec3::new(1.5, 2.0, -300.0)));
+    assert_eq!(from_str::<Point2<f32>>("[0.5, 4]"), Some(Point2::new(0.5f32, 4.0)));
+    assert_eq!(from_str::<Vec4<int>>("[1, 2, 3, 4]"), Some(Vec4::new(1, 2, 3, 4)));
+    assert_eq!(from_str::<Quat<f64>>("[1, 0, 2, 0]"), Some(Quat::new(1.0, 0.0, 2.0, 0.0)));
+    assert_eq!(from_str::<Mat2<f64>>("[[1, 3], [2, 4]]"), Some(Mat2::new(1.0, 2.0, 3.0, 4.0)));
+    assert_eq!(from_str::<Plane<f64>>("[0, 1, 0, 2]"), Some(Plane::from_abcd(0.0, 1.0, 0.0, 2.0)));
+    assert_eq!(from_str::<Rad<f64>>("1.5 rad"), Some(rad(1.5)));
+    assert_eq!(from_str::<Deg<f64>>("90°"), Some(deg(90.0)));
+
+    assert_eq!(from_str::<Vec3<f64>>("[1, 2]"), None);
+    assert_eq!(from_str::<Vec3<f64>>("[1, 2, 3, 4]"), None);
+    assert_eq!(from_str::<Vec3<f64>>("1, 2, 3"), None);
+    assert_eq!(from_str::<Vec3<f64>>("[1, 2, x]"), None);
+    assert_eq!(from_str::<Vec2<f64>>("[1, 2,]"), None);
+    assert_eq!(from_str::<Mat2<f64>>("[[1, 3], [2]]"), None);
+    assert_eq!(from_str::<Mat2<f64>>("[[1, 3], [2, 4]"), None);
+    assert_eq!(from_str::<Deg<f64>>("90"), None);
+}
+
+#[test]
+fn test_round_trip() {
+    let m = Mat4::new(1.0f64, 2.0, 3.0, 4.0,
+                      5.0, 6.0, 7.0, 8.0,
+                      9.0, 10.0, 11.0, 12.0,
+                      13.0, 14.0, 15.0, 16.5);
+    assert_eq!(from_str::<Mat4<f64>>(m.to_str()), Some(m));
+
+    let m = Mat3x4::new(1.0f64, 2.0, 3.0, 4.0,
+                        5.0, 6.0, 7.0, 8.0,
+                        9.0, 10.0, 11.0, 12.0);
+    assert_eq!(from_str::<Mat3x4<f64>>(m.to_str()), Some(m));
+
+    let dq = DualQuat::new(Quat::new(0.5f64, 0.5, 0.5, 0.5), Quat::new(0.0, 1.0, -2.0, 0.25));
+    assert_eq!(from_str::<DualQuat<f64>>(dq.to_str()), Some(dq));
+}
+
+#[test]
+fn test_fields_to_str() {
+    let aabb = Aabb2 { center: Point2::new(1.0f64, 2.0), size: Vec2::new(3.0, 4.0) };
+    assert_eq!(aabb.to_str(), ~"[[1, 2], [3, 4]]");
+    assert_eq!(from_str::<Aabb2<f64>>("[[1, 2], [3, 4]]"), Some(aabb));
+
+    let aabb = Aabb3 { center: Point3::new(0.0f64, 0.5, 1.0), size: Vec3::new(2.0, 2.0, 2.0) };
+    assert_eq!(from_str::<Aabb3<f64>>(aabb.to_str()), Some(aabb));
+
+    let sphere = Sphere { center: Point3::new(1.0f64, 0.0, -1.0), radius: 0.5 };
+    assert_eq!(sphere.to_str(), ~"[[1, 0, -1], 0.5]");
+    assert_eq!(sphere.to_str_digits(1), ~"[[1.0, 0.0, -1.0], 0.5]");
+    assert_eq!(from_str::<Sphere<f64>>("[[1, 0, -1], 0.5]"), Some(sphere));
+
+    let ray = Ray2::new(Point2::new(0.0f64, 1.0), Vec2::new(1.0, 0.0));
+    assert_eq!(ray.to_str(), ~"[[0, 1], [1, 0]]");
+    assert_eq!(from_str::<Ray2<f64>>(ray.to_str()), Some(ray));
+
+    let ray = Ray3::new(Point3::new(0.0f64, 1.0, 2.0), Vec3::new(0.0, 0.0, -1.0));
+    assert_eq!(from_str::<Ray3<f64>>(ray.to_str()), Some(ray));
+
+    assert_eq!(from_str::<Sphere<f64>>("[[1, 0, -1]]"), None);
+    assert_eq!(from_str::<Sphere<f64>>("[[1, 0, -1], 0.5, 2]"), None);
+    assert_eq!(from_str::<Ray2<f64>>("[[0, 1], [1, 0, 0]]"), None);
+}
+
+#[test]
+fn test_rotation_to_str() {
+    let aa = AxisAngle::new(Vec3::new(0.0f64, 0.0, 1.0), deg(90.0f64));
+    assert_eq!(aa.to_str(), ~"[[0, 0, 1], 90°]");
+    assert_eq!(from_str::<AxisAngle<f64, Deg<f64>>>("[[0, 0, 1], 90°]"), Some(aa));
+    assert_eq!(from_str::<AxisAngle<f64, Deg<f64>>>("[[0, 0, 1], 1.5 rad]"), None);
+
+    let euler = Euler::new(deg(30.0f64), deg(0.0), deg(90.0), ZYX, Intrinsic);
+    assert_eq!(euler.to_str(), ~"[30°, 0°, 90°, ZYX, Intrinsic]");
+    assert_eq!(euler.to_str_digits(1), ~"[30.0°, 0.0°, 90.0°, ZYX, Intrinsic]");
+    assert_eq!(from_str::<Euler<Deg<f64>>>("[30°, 0°, 90°, ZYX, Intrinsic]"), Some(euler));
+    assert_eq!(from_str::<Euler<Deg<f64>>>("[30°, 0°, 90°, ZYX]"), None);
+    assert_eq!(from_str::<Euler<Deg<f64>>>("[30°, 0°, 90°, XXY, Intrinsic]"), None);
+    assert_eq!(from_str::<Euler<Deg<f64>>>("[30°, 0°, 90°, ZYX, Inside]"), None);
+
+    for order in [XYZ, XZY, YXZ, YZX, ZXY, ZYX, XYX, XZX, YXY, YZY, ZXZ, ZYZ].iter() {
+        assert_eq!(from_str::<EulerOrder>(order.to_str()), Some(order.clone()));
+    }
+    assert_eq!(from_str::<EulerFrame>(Extrinsic.to_str()), Some(Extrinsic));
+}
+
+#[test]
+fn test_rotation_from_str() {
+    // a quarter turn counter-clockwise, written row by row
+    let rot = from_str::<Rot2<f64>>("[[0, -1], [1, 0]]").unwrap();
+    assert_eq!(rot.as_mat2(), &Mat2::new(0.0, 1.0, -1.0, 0.0));
+    assert_eq!(from_str::<Rot2<f64>>(rot.to_str()), Some(rot));
+
+    let rot = from_str::<Rot3<f64>>("[[1, 0, 0], [0, 0, -1], [0, 1, 0]]").unwrap();
+    assert_eq!(rot.as_mat3(), &Mat3::new(1.0, 0.0, 0.0,
+                                         0.0, 0.0, 1.0,
+                                         0.0, -1.0, 0.0));
+
+    // not orthonormal
+    assert_eq!(from_str::<Rot2<f64>>("[[2, 0], [0, 2]]"), None);
+    assert_eq!(from_str::<Rot3<f64>>("[[1, 1, 0], [0, 1, 0], [0, 0, 1]]"), None);
+    // a reflection
+    assert_eq!(from_str::<Rot2<f64>>("[[0, 1], [1, 0]]"), None);
+    assert_eq!(from_str::<Rot3<f64>>("[[1, 0, 0], [0, 1, 0], [0, 0, -1]]"), None);
+    // not a matrix
+    assert_eq!(from_str::<Rot2<f64>>("[0, 1]"), None);
+}
+
+#[test]
+fn test_split_list() {
+    assert_eq!(split_list("[]"), Some(~[]));
+    assert_eq!(split_list("[a, [b, c], d]"), Some(~["a", "[b, c]", "d"]));
+    assert_eq!(split_list("[a]]"), None);
+}
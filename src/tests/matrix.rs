@@ -361,3 +361,143 @@ fn test_predicates() {
 
     assert!(Mat4::from_value(6.0).is_diagonal());
 }
+
+#[test]
+fn test_polar_decomposition() {
+    use cgmath::angle::*;
+
+    let rot = Mat3::from_angle_z(rad(0.5f64));
+    let stretch = Mat3::new(2.0f64, 0.5, 0.0,
+                            0.5, 3.0, 0.0,
+                            0.0, 0.0, 1.0);
+    let (r, s) = rot.mul_m(&stretch).polar_decomposition().unwrap();
+
+    assert_approx_eq!(r, rot);
+    assert_approx_eq!(s, stretch);
+    assert!(s.is_symmetric());
+
+    assert!(mat3::A.polar_decomposition().is_none());
+}
+
+#[test]
+fn test_nonsquare() {
+    let a = Mat3x2::new(1.0, 2.0,
+                        3.0, 4.0,
+                        5.0, 6.0);
+    let b = Mat2x3::new(1.0, 3.0, 5.0,
+                        2.0, 4.0, 6.0);
+
+    assert_eq!(a.transpose(), b);
+    assert_eq!(b.transpose(), a);
+
+    assert_eq!(a.r(1), Vec3::new(2.0, 4.0, 6.0));
+    assert_eq!(a.mul_v(&Vec3::new(1.0, 1.0, 1.0)), Vec2::new(9.0, 12.0));
+
+    let ab: Mat2<float> = a.mul_mat(&b);
+    assert_eq!(ab, Mat2::new(35.0, 44.0,
+                             44.0, 56.0));
+    let ba: Mat3<float> = b.mul_mat(&a);
+    assert_eq!(ba, Mat3::new( 5.0, 11.0, 17.0,
+                             11.0, 25.0, 39.0,
+                             17.0, 39.0, 61.0));
+
+    let m: Mat3x2<float> = mat2::A.mul_mat(&a);
+    assert_eq!(m, Mat3x2::from_cols(mat2::A.mul_v(a.c(0)),
+                                    mat2::A.mul_v(a.c(1)),
+                                    mat2::A.mul_v(a.c(2))));
+
+    assert_eq!(a.to_mat3(), Mat3::new(1.0, 2.0, 0.0,
+                                      3.0, 4.0, 0.0,
+                                      5.0, 6.0, 1.0));
+}
+
+#[test]
+fn test_mat2_from_angle() {
+    use cgmath::angle::*;
+
+    // a positive angle rotates counter-clockwise, from the x axis towards
+    // the y axis
+    let m: Mat2<f64> = Mat2::from_angle(rad(Real::frac_pi_2()));
+    assert_approx_eq!(m.mul_v(&Vec2::unit_x()), Vec2::unit_y());
+    assert_approx_eq!(m.mul_v(&Vec2::unit_y()), Vec2::new(-1.0, 0.0));
+}
+
+#[test]
+fn test_affine2() {
+    use cgmath::angle::*;
+    use cgmath::point::*;
+
+    let t = Mat3::from_translation2(&Vec2::new(1.0f64, 2.0));
+    let s = Mat3::from_scale2(2.0f64, 3.0);
+    let r = Mat3::from_angle(rad(Real::frac_pi_2()));
+    let p = Point2::new(1.0f64, 1.0);
+    let v = Vec2::new(1.0f64, 1.0);
+
+    assert_eq!(t.transform_point2(&p), Point2::new(2.0, 3.0));
+    assert_eq!(t.transform_vector2(&v), v);
+    assert_eq!(s.transform_point2(&p), Point2::new(2.0, 3.0));
+    assert_approx_eq!(r.transform_point2(&p), Point2::new(-1.0, 1.0));
+    assert_eq!(Mat3::from_shear2(2.0f64, 0.0).transform_vector2(&v), Vec2::new(3.0, 1.0));
+
+    // scale, then rotate, then translate
+    assert_approx_eq!(t.mul_m(&r).mul_m(&s).transform_point2(&p), Point2::new(-2.0, 4.0));
+}
+
+#[test]
+fn test_bulk_transform() {
+    use cgmath::point::*;
+
+    let m = Mat4::new(0.0f64, 1.0, 0.0, 0.0,
+                      -2.0, 0.0, 0.0, 0.0,
+                      0.0, 0.0, 3.0, 0.0,
+                      10.0, 20.0, 30.0, 1.0);
+    let ps = &[Point3::new(1.0f64, 2.0, 3.0), Point3::new(-1.0f64, 0.5, 0.0)];
+    let vs = &[Vec3::new(1.0f64, 2.0, 3.0), Vec3::new(-1.0f64, 0.5, 0.0)];
+
+    let out = &mut [Point3::origin(), Point3::origin()];
+    m.transform_points(ps, out);
+    assert_eq!(out[0], Point3::new(6.0, 21.0, 39.0));
+    assert_eq!(out[1], Point3::new(9.0, 19.0, 30.0));
+
+    let out = &mut [Vec3::zero(), Vec3::zero()];
+    m.transform_vectors(vs, out);
+    assert_eq!(out[0], Vec3::new(-4.0, 1.0, 9.0));
+    assert_eq!(out[1], Vec3::new(-1.0, -1.0, 0.0));
+
+    let m3 = Mat3::new(0.0f64, 1.0, 0.0,
+                       -2.0, 0.0, 0.0,
+                       0.0, 0.0, 3.0);
+    let out = &mut [Vec3::zero(), Vec3::zero()];
+    m3.transform_vectors(vs, out);
+    assert_eq!(out[0], m3.mul_v(&vs[0]));
+    assert_eq!(out[1], m3.mul_v(&vs[1]));
+    let out = &mut [Point3::origin(), Point3::origin()];
+    m3.transform_points(ps, out);
+    assert_eq!(out[0], Point3::new(-4.0, 1.0, 9.0));
+}
+
+#[test]
+fn test_project_points() {
+    use cgmath::point::*;
+
+    // scales by two, and copies -z into w
+    let m = Mat4::new(2.0f64, 0.0, 0.0, 0.0,
+                      0.0, 2.0, 0.0, 0.0,
+                      0.0, 0.0, 1.0, -1.0,
+                      0.0, 0.0, 0.0, 0.0);
+    let ps = &[Point3::new(1.0f64, 2.0, -2.0), Point3::new(-3.0f64, 1.0, -4.0)];
+    let out = &mut [Point3::origin(), Point3::origin()];
+    m.project_points(ps, out);
+    assert_eq!(out[0], Point3::new(1.0, 2.0, -1.0));
+    assert_eq!(out[1], Point3::new(-1.5, 0.5, -1.0));
+}
+
+#[test]
+#[should_fail]
+fn test_bulk_transform_length_mismatch() {
+    use cgmath::point::*;
+
+    let m: Mat4<f64> = Mat4::identity();
+    let out = &mut [Point3::origin()];
+    m.transform_points(&[Point3::new(1.0f64, 2.0, 3.0), Point3::origin()], out);
+}
@@ -0,0 +1,93 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use cgmath::aabb::*;
+use cgmath::angle::*;
+use cgmath::plane::*;
+use cgmath::point::*;
+use cgmath::polyhedron::*;
+use cgmath::projection::*;
+use cgmath::sphere::*;
+use cgmath::vector::*;
+
+// a box from (-1, -2, -3) to (1, 2, 3)
+fn aabb() -> Aabb3<f64> {
+    Aabb3 { center: Point3::new(0.0, 0.0, 0.0), size: Vec3::new(2.0, 4.0, 6.0) }
+}
+
+fn check_box(poly: &ConvexPolyhedron<f64>) {
+    assert!(poly.contains_point(&Point3::new(0.0, 0.0, 0.0)));
+    assert!(poly.contains_point(&Point3::new(1.0, 2.0, 3.0)));
+    assert!(!poly.contains_point(&Point3::new(1.5, 0.0, 0.0)));
+    assert!(!poly.contains_point(&Point3::new(0.0, 0.0, -3.5)));
+
+    assert_eq!(poly.classify_sphere(&Sphere { center: Point3::new(0.0, 0.0, 0.0), radius: 0.5 }), Inside);
+    assert_eq!(poly.classify_sphere(&Sphere { center: Point3::new(1.0, 0.0, 0.0), radius: 0.5 }), Intersecting);
+    assert_eq!(poly.classify_sphere(&Sphere { center: Point3::new(0.0, 5.0, 0.0), radius: 0.5 }), Outside);
+
+    let small = Aabb3 { center: Point3::new(0.0, 1.0, 0.0), size: Vec3::new(1.0, 1.0, 1.0) };
+    let crossing = Aabb3 { center: Point3::new(0.0, 0.0, 3.0), size: Vec3::new(1.0, 1.0, 1.0) };
+    let outside = Aabb3 { center: Point3::new(-3.0, 0.0, 0.0), size: Vec3::new(1.0, 1.0, 1.0) };
+    assert_eq!(poly.classify_aabb3(&small), Inside);
+    assert_eq!(poly.classify_aabb3(&crossing), Intersecting);
+    assert_eq!(poly.classify_aabb3(&outside), Outside);
+}
+
+#[test]
+fn test_from_aabb3() {
+    let poly = ConvexPolyhedron::from_aabb3(&aabb());
+    assert_eq!(poly.planes.len(), 6);
+    check_box(&poly);
+}
+
+#[test]
+fn test_from_points() {
+    let corners = &[Point3::new(-1.0f64, -2.0, -3.0), Point3::new(1.0, -2.0, -3.0),
+                    Point3::new(-1.0,  2.0, -3.0), Point3::new(1.0,  2.0, -3.0),
+                    Point3::new(-1.0, -2.0,  3.0), Point3::new(1.0, -2.0,  3.0),
+                    Point3::new(-1.0,  2.0,  3.0), Point3::new(1.0,  2.0,  3.0),
+                    // a point inside the box, which is not part of the hull
+                    Point3::new(0.1, 0.2, 0.3)];
+    let poly = ConvexPolyhedron::from_points(corners).unwrap();
+    assert_eq!(poly.planes.len(), 6);
+    check_box(&poly);
+
+    // a tetrahedron
+    let tetra = ConvexPolyhedron::from_points(&[Point3::new(0.0f64, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0),
+                                                Point3::new(0.0, 1.0, 0.0), Point3::new(0.0, 0.0, 1.0)]).unwrap();
+    assert_eq!(tetra.planes.len(), 4);
+    assert!(tetra.contains_point(&Point3::new(0.2, 0.2, 0.2)));
+    assert!(!tetra.contains_point(&Point3::new(0.5, 0.5, 0.5)));
+
+    // coplanar points do not enclose a volume
+    assert!(ConvexPolyhedron::from_points(&[Point3::new(0.0f64, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0),
+                                            Point3::new(0.0, 1.0, 0.0), Point3::new(1.0, 1.0, 0.0)]).is_none());
+}
+
+#[test]
+fn test_from_frustum() {
+    let finite = PerspectiveFov::new(deg(90.0f64), 1.0, 1.0, 10.0).unwrap();
+    let poly = ConvexPolyhedron::from_frustum(&finite.to_frustum());
+    assert_eq!(poly.planes.len(), 6);
+    assert!(poly.contains_point(&Point3::new(0.0, 0.0, -5.0)));
+    assert!(!poly.contains_point(&Point3::new(0.0, 0.0, -0.5)));
+    assert!(!poly.contains_point(&Point3::new(0.0, 0.0, -11.0)));
+    assert!(!poly.contains_point(&Point3::new(6.0, 0.0, -5.0)));
+
+    let infinite = PerspectiveFovDepth::new(deg(90.0f64), 1.0, 1.0, None, NegativeOneToOne).unwrap();
+    let poly = ConvexPolyhedron::from_frustum(&infinite.to_frustum());
+    assert_eq!(poly.planes.len(), 5);
+    assert!(poly.contains_point(&Point3::new(0.0, 0.0, -1.0e6)));
+}
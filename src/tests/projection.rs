@@ -0,0 +1,317 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use cgmath::angle::*;
+use cgmath::frustum::*;
+use cgmath::matrix::*;
+use cgmath::plane::*;
+use cgmath::point::*;
+use cgmath::projection::*;
+use cgmath::vector::*;
+
+// a perspective projection with a vertical field of view of 90 degrees, an
+// aspect ratio of 1, and near and far planes at 1 and 10
+fn persp() -> Mat4<f64> {
+    Mat4::new(1.0, 0.0,  0.0,         0.0,
+              0.0, 1.0,  0.0,         0.0,
+              0.0, 0.0, -11.0 / 9.0, -1.0,
+              0.0, 0.0, -20.0 / 9.0,  0.0)
+}
+
+// the same projection, mapping depth to `[0, 1]` as in Direct3D
+fn persp_zero_to_one() -> Mat4<f64> {
+    Mat4::new(1.0, 0.0,  0.0,         0.0,
+              0.0, 1.0,  0.0,         0.0,
+              0.0, 0.0, -10.0 / 9.0, -1.0,
+              0.0, 0.0, -10.0 / 9.0,  0.0)
+}
+
+#[test]
+fn test_project() {
+    let view = Mat4::<f64>::identity();
+    let viewport = Viewport::new(0.0f64, 0.0, 800.0, 600.0);
+
+    assert_approx_eq!(project(&Point3::new(0.0, 0.0, -1.0), &view, &persp(), &viewport, NegativeOneToOne),
+                      Point3::new(400.0, 300.0, 0.0));
+    assert_approx_eq!(project(&Point3::new(0.0, 0.0, -10.0), &view, &persp(), &viewport, NegativeOneToOne),
+                      Point3::new(400.0, 300.0, 1.0));
+    assert_approx_eq!(project(&Point3::new(1.0, 1.0, -2.0), &view, &persp(), &viewport, NegativeOneToOne),
+                      Point3::new(600.0, 450.0, 5.0 / 9.0));
+
+    assert_approx_eq!(project(&Point3::new(0.0, 0.0, -1.0), &view, &persp_zero_to_one(), &viewport, ZeroToOne),
+                      Point3::new(400.0, 300.0, 0.0));
+    assert_approx_eq!(project(&Point3::new(0.0, 0.0, -10.0), &view, &persp_zero_to_one(), &viewport, ZeroToOne),
+                      Point3::new(400.0, 300.0, 1.0));
+    assert_approx_eq!(project(&Point3::new(1.0, 1.0, -2.0), &view, &persp_zero_to_one(), &viewport, ZeroToOne),
+                      Point3::new(600.0, 450.0, 5.0 / 9.0));
+    assert_approx_eq!(persp_zero_to_one(), perspective_depth(deg(90.0f64), 1.0, 1.0, Some(10.0), ZeroToOne));
+}
+
+#[test]
+fn test_unproject() {
+    let view = Mat4::new(1.0f64, 0.0, 0.0, 0.0,
+                         0.0, 1.0, 0.0, 0.0,
+                         0.0, 0.0, 1.0, 0.0,
+                         1.0, -2.0, 3.0, 1.0);
+    let viewport = Viewport::new(10.0f64, 20.0, 640.0, 480.0);
+    let p = Point3::new(0.5f64, 1.5, -7.0);
+
+    for &depth in [NegativeOneToOne, ZeroToOne].iter() {
+        let window = project(&p, &view, &persp(), &viewport, depth);
+        assert_approx_eq!(unproject(&window, &view, &persp(), &viewport, depth).unwrap(), p);
+    }
+    assert!(unproject(&Point3::new(0.0, 0.0, 0.5), &Mat4::zero(), &persp(), &viewport, ZeroToOne).is_none());
+}
+
+#[test]
+fn test_pick_ray() {
+    let view = Mat4::<f64>::identity();
+    let viewport = Viewport::new(0.0f64, 0.0, 800.0, 600.0);
+
+    let ray = pick_ray(400.0, 300.0, &view, &persp(), &viewport, NegativeOneToOne).unwrap();
+    assert_approx_eq!(ray.origin, Point3::new(0.0, 0.0, -1.0));
+    assert_approx_eq!(ray.direction, Vec3::new(0.0, 0.0, -1.0));
+
+    let ray = pick_ray(600.0, 450.0, &view, &persp(), &viewport, NegativeOneToOne).unwrap();
+    assert_approx_eq!(ray.origin, Point3::new(0.5, 0.5, -1.0));
+    assert_approx_eq!(ray.direction, Vec3::new(1.0, 1.0, -2.0).normalize());
+}
+
+// the depth that a point at `z` in view space is mapped to
+fn ndc_depth(proj: &Mat4<f64>, z: f64) -> f64 {
+    let clip = proj.mul_v(&Vec4::new(0.0, 0.0, z, 1.0));
+    clip.z / clip.w
+}
+
+#[test]
+fn test_perspective_depth() {
+    let fovy = deg(90.0f64);
+
+    let gl = perspective_depth(fovy, 1.0, 1.0, Some(10.0), NegativeOneToOne);
+    assert_approx_eq!(gl, persp());
+
+    let zo = perspective_depth(fovy, 1.0, 1.0, Some(10.0), ZeroToOne);
+    assert_approx_eq!(ndc_depth(&zo, -1.0), 0.0);
+    assert_approx_eq!(ndc_depth(&zo, -10.0), 1.0);
+
+    let rev = perspective_depth(fovy, 1.0, 1.0, Some(10.0), OneToZero);
+    assert_approx_eq!(ndc_depth(&rev, -1.0), 1.0);
+    assert_approx_eq!(ndc_depth(&rev, -10.0), 0.0);
+}
+
+#[test]
+fn test_perspective_depth_infinite() {
+    let fovy = deg(90.0f64);
+
+    let gl = perspective_depth(fovy, 1.0, 1.0, None, NegativeOneToOne);
+    assert_approx_eq!(ndc_depth(&gl, -1.0), -1.0);
+    assert!(ndc_depth(&gl, -1.0e8) < 1.0);
+    assert_approx_eq!(ndc_depth(&gl, -1.0e8), 1.0);
+
+    let zo = perspective_depth(fovy, 1.0, 1.0, None, ZeroToOne);
+    assert_approx_eq!(ndc_depth(&zo, -1.0), 0.0);
+    assert_approx_eq!(ndc_depth(&zo, -1.0e8), 1.0);
+
+    let rev = perspective_depth(fovy, 1.0, 1.0, None, OneToZero);
+    assert_approx_eq!(ndc_depth(&rev, -1.0), 1.0);
+    assert!(ndc_depth(&rev, -1.0e8) > 0.0);
+    assert_approx_eq!(ndc_depth(&rev, -1.0e8), 0.0);
+}
+
+#[test]
+fn test_perspective_depth_frustum() {
+    for &depth in [NegativeOneToOne, ZeroToOne, OneToZero].iter() {
+        let finite = PerspectiveFovDepth { fovy: deg(90.0f64), aspect: 1.0, near: 1.0, far: Some(10.0), depth: depth };
+        let frustum = finite.to_frustum();
+        assert_approx_eq!(frustum.near, Plane::from_abcd(0.0, 0.0, -1.0, -1.0));
+        assert_approx_eq!(frustum.far.unwrap(), Plane::from_abcd(0.0, 0.0, 1.0, 10.0));
+        assert_approx_eq!(frustum.left, Plane::from_abcd(0.7071067811865476, 0.0, -0.7071067811865476, 0.0));
+
+        let infinite = PerspectiveFovDepth { fovy: deg(90.0f64), aspect: 1.0, near: 1.0, far: None, depth: depth };
+        let frustum = infinite.to_frustum();
+        assert_approx_eq!(frustum.near, Plane::from_abcd(0.0, 0.0, -1.0, -1.0));
+        assert!(frustum.far.is_none());
+    }
+}
+
+#[test]
+fn test_perspective_depth_off_center() {
+    for &depth in [NegativeOneToOne, ZeroToOne, OneToZero].iter() {
+        // a symmetric frustum is the same as the field of view projection
+        assert_approx_eq!(frustum_depth(-1.0f64, 1.0, -1.0, 1.0, 1.0, Some(10.0), depth),
+                          perspective_depth(deg(90.0f64), 1.0, 1.0, Some(10.0), depth));
+        assert_approx_eq!(frustum_depth(-1.0f64, 1.0, -1.0, 1.0, 1.0, None, depth),
+                          perspective_depth(deg(90.0f64), 1.0, 1.0, None, depth));
+    }
+
+    assert_approx_eq!(frustum_depth(-1.0f64, 3.0, -2.0, 1.0, 1.0, Some(10.0), NegativeOneToOne),
+                      frustum(-1.0f64, 3.0, -2.0, 1.0, 1.0, 10.0));
+
+    let proj = PerspectiveDepth::new(-1.0f64, 3.0, -2.0, 1.0, 1.0, None, OneToZero).unwrap();
+    assert_approx_eq!(ndc_depth(&proj.to_mat4(), -1.0), 1.0);
+    assert!(proj.to_frustum().far.is_none());
+
+    assert_eq!(PerspectiveDepth::new(1.0f64, -1.0, -1.0, 1.0, 1.0, None, ZeroToOne), Err(LeftBeyondRight));
+    assert_eq!(PerspectiveDepth::new(-1.0f64, 1.0, -1.0, 1.0, 0.0, None, ZeroToOne), Err(NegativeNear));
+}
+
+#[test]
+fn test_pick_ray_reversed() {
+    let proj = perspective_depth(deg(90.0f64), 1.0, 1.0, None, OneToZero);
+    let view = Mat4::<f64>::identity();
+    let viewport = Viewport::new(0.0f64, 0.0, 800.0, 600.0);
+
+    let ray = pick_ray(400.0, 300.0, &view, &proj, &viewport, OneToZero).unwrap();
+    assert_approx_eq!(ray.origin, Point3::new(0.0, 0.0, -1.0));
+    assert_approx_eq!(ray.direction, Vec3::new(0.0, 0.0, -1.0));
+}
+
+#[test]
+fn test_perspective_fov_validate() {
+    assert!(PerspectiveFov::new(deg(90.0f64), 1.0, 1.0, 10.0).is_ok());
+    assert_eq!(PerspectiveFov::new(deg(-90.0f64), 1.0, 1.0, 10.0), Err(NegativeFov));
+    assert_eq!(PerspectiveFov::new(deg(0.0f64), 1.0, 1.0, 10.0), Err(NegativeFov));
+    assert_eq!(PerspectiveFov::new(deg(180.0f64), 1.0, 1.0, 10.0), Err(FovOverHalfTurn));
+    assert_eq!(PerspectiveFov::new(deg(90.0f64), 0.0, 1.0, 10.0), Err(NegativeAspect));
+    assert_eq!(PerspectiveFov::new(deg(90.0f64), 1.0, 0.0, 10.0), Err(NegativeNear));
+    assert_eq!(PerspectiveFov::new(deg(90.0f64), 1.0, 10.0, 10.0), Err(NearBeyondFar));
+
+    assert!(PerspectiveFovDepth::new(deg(90.0f64), 1.0, 1.0, None, OneToZero).is_ok());
+    assert_eq!(PerspectiveFovDepth::new(deg(90.0f64), 1.0, -1.0, None, OneToZero), Err(NegativeNear));
+    assert_eq!(PerspectiveFovDepth::new(deg(90.0f64), 1.0, 2.0, Some(1.0), ZeroToOne), Err(NearBeyondFar));
+}
+
+#[test]
+fn test_perspective_validate() {
+    assert!(Perspective::new(-1.0f64, 1.0, -1.0, 1.0, 1.0, 10.0).is_ok());
+    assert_eq!(Perspective::new(1.0f64, -1.0, -1.0, 1.0, 1.0, 10.0), Err(LeftBeyondRight));
+    assert_eq!(Perspective::new(-1.0f64, 1.0, 1.0, 1.0, 1.0, 10.0), Err(BottomBeyondTop));
+    assert_eq!(Perspective::new(-1.0f64, 1.0, -1.0, 1.0, -1.0, 10.0), Err(NegativeNear));
+    assert_eq!(Perspective::new(-1.0f64, 1.0, -1.0, 1.0, 10.0, 1.0), Err(NearBeyondFar));
+}
+
+#[test]
+fn test_ortho_validate() {
+    assert!(Ortho::new(-1.0f64, 1.0, -1.0, 1.0, -1.0, 1.0).is_ok());
+    assert_eq!(Ortho::new(1.0f64, 1.0, -1.0, 1.0, -1.0, 1.0), Err(LeftBeyondRight));
+    assert_eq!(Ortho::new(-1.0f64, 1.0, 1.0, -1.0, -1.0, 1.0), Err(BottomBeyondTop));
+    assert_eq!(Ortho::new(-1.0f64, 1.0, -1.0, 1.0, 1.0, -1.0), Err(NearBeyondFar));
+}
+
+#[test]
+fn test_perspective() {
+    assert_approx_eq!(perspective(deg(90.0f64), 1.0, 1.0, 10.0), persp());
+    assert_approx_eq!(frustum(-1.0f64, 1.0, -1.0, 1.0, 1.0, 10.0), persp());
+}
+
+#[test]
+fn test_ortho() {
+    let proj = Ortho::new(-2.0f64, 2.0, -1.0, 1.0, 1.0, 10.0).unwrap();
+    let m = proj.to_mat4();
+    assert_approx_eq!(m.mul_v(&Vec4::new(2.0, 1.0, -1.0, 1.0)), Vec4::new(1.0, 1.0, -1.0, 1.0));
+    assert_approx_eq!(m.mul_v(&Vec4::new(-2.0, -1.0, -10.0, 1.0)), Vec4::new(-1.0, -1.0, 1.0, 1.0));
+
+    let frustum = proj.to_frustum();
+    let from_mat = Frustum::from_mat4(m);
+    assert_approx_eq!(frustum.left, from_mat.left);
+    assert_approx_eq!(frustum.right, from_mat.right);
+    assert_approx_eq!(frustum.bottom, from_mat.bottom);
+    assert_approx_eq!(frustum.top, from_mat.top);
+    assert_approx_eq!(frustum.near, from_mat.near);
+    assert_approx_eq!(frustum.far.unwrap(), from_mat.far.unwrap());
+}
+
+#[test]
+#[should_fail]
+fn test_perspective_invalid() {
+    perspective(deg(90.0f64), 1.0, 10.0, 1.0);
+}
+
+#[test]
+#[should_fail]
+fn test_ortho_invalid() {
+    ortho(1.0f64, -1.0, -1.0, 1.0, -1.0, 1.0);
+}
+
+#[test]
+fn test_oblique_near_plane() {
+    // a plane tilted away from the viewer, passing through (0.5, 0.2, -3)
+    let plane = Plane::from_abcd(0.3f64, 0.2, -1.0, -3.19);
+    let on_plane = [Vec4::new(0.5, 0.2, -3.0, 1.0), Vec4::new(1.5, 0.2, -2.7, 1.0)];
+    let behind = Vec4::new(0.5, 0.2, -4.0, 1.0);
+
+    for &(depth, near, far) in [(NegativeOneToOne, -1.0, 1.0),
+                                (ZeroToOne, 0.0, 1.0),
+                                (OneToZero, 1.0, 0.0)].iter() {
+        let proj = perspective_depth(deg(90.0f64), 1.0, 1.0, Some(10.0), depth);
+        let oblique = oblique_near_plane(&proj, &plane, depth).unwrap();
+
+        for p in on_plane.iter() {
+            let clip = oblique.mul_v(p);
+            assert_approx_eq!(clip.z / clip.w, near);
+        }
+
+        let clip = oblique.mul_v(&behind);
+        let z = clip.z / clip.w;
+        assert!((z - near) * (z - far) < 0.0);
+    }
+}
+
+#[test]
+fn test_halton() {
+    assert_approx_eq!(halton::<f64>(0, 2), 0.0);
+    assert_approx_eq!(halton::<f64>(1, 2), 0.5);
+    assert_approx_eq!(halton::<f64>(2, 2), 0.25);
+    assert_approx_eq!(halton::<f64>(3, 2), 0.75);
+    assert_approx_eq!(halton::<f64>(4, 2), 0.125);
+    assert_approx_eq!(halton::<f64>(1, 3), 1.0 / 3.0);
+    assert_approx_eq!(halton::<f64>(2, 3), 2.0 / 3.0);
+    assert_approx_eq!(halton::<f64>(3, 3), 1.0 / 9.0);
+
+    let (x, y) = halton_jitter::<f64>(0);
+    assert_approx_eq!(x, 0.0);
+    assert_approx_eq!(y, 1.0 / 3.0 - 0.5);
+}
+
+#[test]
+fn test_jitter() {
+    let proj = PerspectiveFov::new(deg(90.0f64), 2.0, 1.0, 10.0).unwrap();
+    let viewport = Viewport::new(0.0f64, 0.0, 800.0, 400.0);
+    let view = Mat4::<f64>::identity();
+    let p = Point3::new(0.3f64, -0.2, -4.0);
+
+    let before = project(&p, &view, &proj.to_mat4(), &viewport, NegativeOneToOne);
+    let after = project(&p, &view, &proj.jitter(0.25, -0.5, 800.0, 400.0).to_mat4(), &viewport, NegativeOneToOne);
+    assert_approx_eq!(after.x - before.x, 0.25);
+    assert_approx_eq!(after.y - before.y, -0.5);
+    assert_approx_eq!(after.z, before.z);
+}
+
+#[test]
+fn test_stereo() {
+    let proj = PerspectiveFov::new(deg(90.0f64), 1.0, 1.0, 10.0).unwrap();
+    let (left, right) = proj.stereo(0.064, 2.0);
+
+    // the point of convergence is at the center of both images
+    let l = left.to_mat4().mul_v(&Vec4::new(0.032, 0.0, -2.0, 1.0));
+    let r = right.to_mat4().mul_v(&Vec4::new(-0.032, 0.0, -2.0, 1.0));
+    assert_approx_eq!(l.x / l.w, 0.0);
+    assert_approx_eq!(r.x / r.w, 0.0);
+
+    // both frustums keep the width of the original
+    assert_approx_eq!(left.right - left.left, 2.0);
+    assert_approx_eq!(right.right - right.left, 2.0);
+    assert!(left.left > right.left);
+}
@@ -0,0 +1,173 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use cgmath::angle::*;
+use cgmath::quaternion::*;
+use cgmath::vector::*;
+
+#[test]
+fn test_renormalize() {
+    let q = Quat::new(2.0f64, 0.0, 0.0, 0.0);
+    assert!(!q.is_normalized());
+    assert_approx_eq!(q.renormalize().unwrap(), Quat::identity());
+    assert!(q.renormalize().unwrap().is_normalized());
+
+    assert!(Quat::new(0.0f64, 0.0, 0.0, 0.0).renormalize().is_none());
+}
+
+#[test]
+fn test_from_arc() {
+    let pairs = [(Vec3::new(1.0f64, 0.0, 0.0), Vec3::new(0.0f64, 2.0, 0.0)),
+                 (Vec3::new(1.0f64, 2.0, 3.0), Vec3::new(1.0f64, 2.0, 3.0 + 1.0e-7)),
+                 (Vec3::new(1.0f64, 0.0, 0.0), Vec3::new(-1.0f64, 0.0, 0.0)),
+                 (Vec3::new(1.0f64, 2.0, 3.0), Vec3::new(-1.0f64, -2.0, -3.0))];
+
+    for &(src, dst) in pairs.iter() {
+        let q = Quat::from_arc(&src, &dst);
+        assert!(q.is_normalized());
+        assert_approx_eq!(q.mul_v(&src.normalize()), dst.normalize());
+    }
+}
+
+#[test]
+fn test_from_arc_up() {
+    let up = Vec3::<f64>::unit_y();
+    let src = Vec3::<f64>::unit_z();
+    let dst = Vec3::new(1.0f64, 1.0, 0.0);
+    let q = Quat::from_arc_up(&src, &dst, &up);
+
+    assert_approx_eq!(q.mul_v(&src), dst.normalize());
+    // the side vector stays horizontal
+    assert_approx_eq!(q.mul_v(&Vec3::unit_x()).y, 0.0);
+
+    // parallel to `up` falls back to the shortest arc
+    assert_approx_eq!(Quat::from_arc_up(&src, &up, &up), Quat::from_arc(&src, &up));
+}
+
+fn rot_z(degrees: f64) -> Quat<f64> {
+    Quat::from_axis_angle(&Vec3::unit_z(), deg(degrees))
+}
+
+#[test]
+fn test_exp_ln() {
+    let q = Quat::from_axis_angle(&Vec3::new(0.0f64, 0.6, 0.8), deg(70.0f64));
+    assert_approx_eq!(q.ln().exp(), q);
+    assert_approx_eq!(q.ln().s, 0.0);
+    assert_approx_eq!(Quat::<f64>::identity().ln(), Quat::zero());
+    assert_approx_eq!(Quat::<f64>::zero().exp(), Quat::identity());
+}
+
+#[test]
+fn test_powf() {
+    assert_approx_eq!(rot_z(90.0).powf(0.5), rot_z(45.0));
+    assert_approx_eq!(rot_z(30.0).powf(3.0), rot_z(90.0));
+}
+
+#[test]
+fn test_slerp() {
+    assert_approx_eq!(rot_z(0.0).slerp(&rot_z(90.0), 0.5), rot_z(45.0));
+    // `-q` is the same rotation as `q`, so the same path should be taken
+    assert_approx_eq!(rot_z(0.0).slerp(&-rot_z(90.0), 0.5), rot_z(45.0));
+}
+
+#[test]
+fn test_squad() {
+    let keys = [rot_z(0.0), rot_z(30.0), rot_z(60.0), rot_z(90.0)];
+    let a = keys[1].squad_control(&keys[0], &keys[2]);
+    let b = keys[2].squad_control(&keys[1], &keys[3]);
+
+    // evenly spaced keyframes along a great arc need no correction
+    assert_approx_eq!(a, keys[1]);
+    assert_approx_eq!(keys[1].squad(&keys[2], &a, &b, 0.0), keys[1]);
+    assert_approx_eq!(keys[1].squad(&keys[2], &a, &b, 0.5), rot_z(45.0));
+    assert_approx_eq!(keys[1].squad(&keys[2], &a, &b, 1.0), keys[2]);
+}
+
+#[test]
+fn test_angle_between() {
+    assert_approx_eq!(rot_z(10.0).angle_between(&rot_z(100.0)), rad(Real::frac_pi_2()));
+    assert_approx_eq!(rot_z(10.0).angle_between(&-rot_z(100.0)), rad(Real::frac_pi_2()));
+}
+
+#[test]
+fn test_ln_near_negative() {
+    // a full turn around `x`, with the vector part almost vanished
+    let q = Quat::new(-1.0f64, 1.0e-7, 0.0, 0.0);
+    assert_approx_eq!(q.ln().v, Vec3::new(Real::pi(), 0.0, 0.0));
+    assert_approx_eq!(q.powf(0.5), Quat::new(0.0, 1.0, 0.0, 0.0));
+
+    // the zero quaternion has no axis
+    let zero = Quat::<f64>::zero().ln();
+    assert!(zero.s.is_infinite());
+    assert_eq!(zero.v, Vec3::zero());
+}
+
+#[test]
+fn test_swing_twist() {
+    let swing = Quat::from_axis_angle(&Vec3::unit_x(), deg(30.0f64));
+    let twist = Quat::from_axis_angle(&Vec3::unit_z(), deg(40.0f64));
+    let (s, t) = swing.mul_q(&twist).swing_twist(&Vec3::unit_z());
+
+    assert_approx_eq!(s, swing);
+    assert_approx_eq!(t, twist);
+}
+
+#[test]
+fn test_swing_twist_singular() {
+    // a half turn around an axis perpendicular to the twist axis
+    let q = Quat::from_axis_angle(&Vec3::unit_x(), deg(180.0f64));
+    let (s, t) = q.swing_twist(&Vec3::unit_z());
+
+    assert_approx_eq!(t, Quat::identity());
+    assert_approx_eq!(s, q);
+    assert_approx_eq!(s.mul_q(&t), q);
+
+    let q = Quat::from_axis_angle(&Vec3::new(0.6f64, 0.8, 0.0), deg(180.0f64));
+    let (s, t) = q.swing_twist(&Vec3::unit_z());
+    assert_approx_eq!(s.mul_q(&t), q);
+}
+
+#[test]
+fn test_swing_twist_limits() {
+    let axis = Vec3::unit_z();
+    let swing = Quat::from_axis_angle(&Vec3::unit_x(), deg(60.0f64));
+    let twist = Quat::from_axis_angle(&axis, deg(-50.0f64));
+    let q = swing.mul_q(&twist);
+
+    assert_approx_eq!(swing.limit_angle(deg(45.0f64)),
+                      Quat::from_axis_angle(&Vec3::unit_x(), deg(45.0f64)));
+    assert_approx_eq!(swing.limit_angle(deg(90.0f64)), swing);
+
+    assert_approx_eq!(q.limit_twist(&axis, deg(-20.0f64), deg(20.0f64)),
+                      swing.mul_q(&Quat::from_axis_angle(&axis, deg(-20.0f64))));
+
+    assert_approx_eq!(q.limit_swing_twist(&axis, deg(45.0f64), deg(-20.0f64), deg(20.0f64)),
+                      Quat::from_axis_angle(&Vec3::unit_x(), deg(45.0f64))
+                          .mul_q(&Quat::from_axis_angle(&axis, deg(-20.0f64))));
+}
+
+#[test]
+fn test_integrate() {
+    let q = Quat::from_axis_angle(&Vec3::unit_x(), deg(30.0f64));
+    let omega = Vec3::new(0.0f64, 0.0, 2.0);
+
+    assert_approx_eq!(q.integrate(&omega, 0.25),
+                      Quat::from_axis_angle(&Vec3::unit_z(), rad(0.5f64)).mul_q(&q));
+    assert_approx_eq!(q.angular_velocity(&q.integrate(&omega, 0.25), 0.25), omega);
+
+    // the approximation converges on the exact result for small timesteps
+    let dt = 1.0e-4;
+    assert_approx_eq!(q.integrate_approx(&omega, dt), q.integrate(&omega, dt));
+}
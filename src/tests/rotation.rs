@@ -0,0 +1,147 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use cgmath::angle::*;
+use cgmath::rotation::*;
+
+#[test]
+fn test_orthonormalize() {
+    use cgmath::matrix::*;
+    use cgmath::quaternion::*;
+    use cgmath::vector::*;
+
+    let step = Rot3::from_angle_x(rad(0.1f32)).concat(&Rot3::from_angle_y(rad(0.2f32)));
+    let mut rot = Rot3::from_angle_z(rad(0.0f32));
+
+    // the same rotations composed as quaternions, which are renormalized at
+    // each step and so do not drift
+    let q_step = Quat::from_axis_angle(&Vec3::unit_x(), rad(0.1f32))
+                      .mul_q(&Quat::from_axis_angle(&Vec3::unit_y(), rad(0.2f32)));
+    let mut q = Quat::identity();
+
+    for _ in range(0, 1000) {
+        rot.concat_self(&step);
+        q = q.mul_q(&q_step).normalize();
+    }
+    assert!(!rot.is_orthonormal());
+
+    rot.orthonormalize_self();
+    assert!(rot.is_orthonormal());
+    assert!(rot.as_mat3().approx_eq_eps(&q.to_mat3(), &1.0e-4));
+}
+
+#[test]
+fn test_rot2() {
+    use cgmath::matrix::*;
+    use cgmath::point::*;
+    use cgmath::ray::*;
+    use cgmath::vector::*;
+
+    let rot = Rot2::from_angle(deg(90.0f64));
+
+    assert_approx_eq!(rot.rotate_vec2(&Vec2::unit_x()), Vec2::unit_y());
+    assert_approx_eq!(rot.rotate_point2(&Point2::new(1.0, 2.0)), Point2::new(-2.0, 1.0));
+
+    let ray = rot.rotate_ray2(&Ray2::new(Point2::new(1.0, 0.0), Vec2::unit_y()));
+    assert_approx_eq!(ray.origin, Point2::new(0.0, 1.0));
+    assert_approx_eq!(ray.direction, Vec2::new(-1.0, 0.0));
+
+    assert_approx_eq!(rot.concat(&rot.invert()).to_mat2(), Mat2::identity());
+}
+
+#[test]
+fn test_euler() {
+    use cgmath::matrix::*;
+
+    let orders = [XYZ, XZY, YXZ, YZX, ZXY, ZYX, XYX, XZX, YXY, YZY, ZXZ, ZYZ];
+    let frames = [Intrinsic, Extrinsic];
+    // the last two sets of angles cause gimbal lock for the Tait-Bryan and
+    // proper euler orders respectively
+    let angles = [(0.3f64, -1.2f64, 2.1f64),
+                  (0.3f64, Real::frac_pi_2(), 2.1f64),
+                  (0.3f64, 0.0f64, 2.1f64)];
+
+    for order in orders.iter() {
+        for frame in frames.iter() {
+            for &(a, b, c) in angles.iter() {
+                let mat = Euler::new(rad(a), rad(b), rad(c), order.clone(), frame.clone()).to_mat3();
+                let euler: Euler<Rad<f64>> = Euler::from_mat3(&mat, order.clone(), frame.clone());
+                assert_approx_eq!(euler.to_mat3(), mat);
+            }
+        }
+    }
+
+    let (x, y, z) = (rad(0.1f64), rad(0.2f64), rad(0.3f64));
+    assert_approx_eq!(Euler::new(x, y, z, XYZ, Extrinsic).to_mat3(), Mat3::from_euler(x, y, z));
+    assert_approx_eq!(Euler::new(z, y, x, ZYX, Intrinsic).to_mat3(), Mat3::from_euler(x, y, z));
+}
+
+#[test]
+fn test_axis_angle() {
+    use cgmath::matrix::*;
+    use cgmath::quaternion::*;
+    use cgmath::vector::*;
+
+    let axis = Vec3::new(1.0f64, 2.0, 2.0).div_s(3.0);
+    let angles = [rad(1.0e-4f64), rad(1.0f64), rad(Real::pi() - 1.0e-4)];
+
+    for angle in angles.iter() {
+        let rot = AxisAngle::new(axis, *angle);
+        let from_quat: AxisAngle<f64, Rad<f64>> = AxisAngle::from_quat(&rot.to_quat());
+        let from_mat3: AxisAngle<f64, Rad<f64>> = AxisAngle::from_mat3(&rot.to_mat3());
+
+        assert_approx_eq!(from_quat, rot);
+        assert_approx_eq!(from_mat3, rot);
+        assert_approx_eq!(rot.rotate_vec3(&Vec3::unit_x()), rot.to_quat().mul_v(&Vec3::unit_x()));
+    }
+
+    let no_rotation: AxisAngle<f64, Rad<f64>> = AxisAngle::from_quat(&Quat::identity());
+    assert_approx_eq!(no_rotation.angle, rad(0.0));
+
+    let v = Vec3::new(0.0f64, 0.0, 0.5);
+    let scaled: AxisAngle<f64, Rad<f64>> = AxisAngle::from_scaled_axis(&v);
+    assert_approx_eq!(scaled, AxisAngle::new(Vec3::unit_z(), rad(0.5)));
+    assert_approx_eq!(scaled.to_scaled_axis(), v);
+}
+
+#[test]
+fn test_rotate_points3() {
+    use cgmath::point::*;
+    use cgmath::quaternion::*;
+    use cgmath::vector::*;
+
+    let rot = Rot3::from_angle_z(deg(90.0f64));
+    let quat = rot.to_quat();
+
+    let ps = &[Point3::new(1.0f64, 2.0, 3.0), Point3::new(0.0f64, -1.0, 0.5)];
+    let vs = &[Vec3::unit_x(), Vec3::new(0.0f64, -1.0, 0.5)];
+    let expected_ps = [Point3::new(-2.0f64, 1.0, 3.0), Point3::new(1.0f64, 0.0, 0.5)];
+    let out = &mut [Point3::origin(), Point3::origin()];
+    let vout = &mut [Vec3::zero(), Vec3::zero()];
+
+    rot.rotate_points3(ps, out);
+    rot.rotate_vecs3(vs, vout);
+    for i in range(0u, 2) {
+        assert_approx_eq!(out[i], expected_ps[i]);
+        assert_approx_eq!(vout[i], rot.rotate_vec3(&vs[i]));
+    }
+
+    quat.rotate_points3(ps, out);
+    quat.rotate_vecs3(vs, vout);
+    for i in range(0u, 2) {
+        assert_approx_eq!(out[i], expected_ps[i]);
+        assert_approx_eq!(vout[i], quat.rotate_vec3(&vs[i]));
+    }
+}
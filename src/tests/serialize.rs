@@ -0,0 +1,139 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io;
+
+use extra::json;
+use extra::serialize::{Encodable, Decodable};
+
+use cgmath::aabb::*;
+use cgmath::angle::*;
+use cgmath::matrix::*;
+use cgmath::plane::*;
+use cgmath::point::*;
+use cgmath::quaternion::*;
+use cgmath::ray::*;
+use cgmath::serialize::*;
+use cgmath::sphere::*;
+use cgmath::vector::*;
+
+fn to_json<T: Encodable<json::Encoder>>(x: &T) -> ~str {
+    do io::with_str_writer |wr| {
+        let mut encoder = json::Encoder(wr);
+        x.encode(&mut encoder);
+    }
+}
+
+fn from_json<T: Decodable<json::Decoder>>(s: &str) -> T {
+    let mut decoder = json::Decoder(json::from_str(s).unwrap());
+    Decodable::decode(&mut decoder)
+}
+
+#[test]
+fn test_json_layout() {
+    assert_eq!(to_json(&Vec3::new(1.0f64, 2.0, 3.0)), ~"[1,2,3]");
+    assert_eq!(to_json(&Quat::new(1.0f64, 2.0, 3.0, 4.0)), ~"[1,2,3,4]");
+    assert_eq!(to_json(&Mat2::new(1.0f64, 2.0, 3.0, 4.0)), ~"[[1,2],[3,4]]");
+    assert_eq!(to_json(&rad(0.5f64)), ~"0.5");
+    assert_eq!(to_json(&Plane::from_abcd(0.0f64, 1.0, 0.0, 2.0)), ~"{\"n\":[0,1,0],\"d\":2}");
+}
+
+#[test]
+fn test_json_round_trip_f32() {
+    let v = Vec4::new(1.5f32, -2.25, 0.0, 8.0);
+    let p = Point2::new(0.5f32, -0.75);
+    let q = Quat::new(0.5f32, 0.5, -0.5, 0.5);
+    let m = Mat3::new(1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+    let r = Ray3::new(Point3::new(1.0f32, 2.0, 3.0), Vec3::new(0.0f32, 0.0, -1.0));
+    let s = Sphere { center: Point3::new(1.0f32, 0.0, -1.0), radius: 2.5f32 };
+
+    assert_eq!(from_json::<Vec4<f32>>(to_json(&v)), v);
+    assert_eq!(from_json::<Point2<f32>>(to_json(&p)), p);
+    assert_eq!(from_json::<Quat<f32>>(to_json(&q)), q);
+    assert_eq!(from_json::<Mat3<f32>>(to_json(&m)), m);
+    assert_eq!(from_json::<Ray3<f32>>(to_json(&r)), r);
+    assert_eq!(from_json::<Sphere<f32>>(to_json(&s)), s);
+    assert_eq!(from_json::<Deg<f32>>(to_json(&deg(90.0f32))), deg(90.0f32));
+}
+
+#[test]
+fn test_json_round_trip_f64() {
+    let v = Vec2::new(1.5f64, -2.25);
+    let m = Mat4::new(1.0f64, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0,
+                      9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0);
+    let m23 = Mat2x3::new(1.0f64, 2.0, 3.0, 4.0, 5.0, 6.0);
+    let a = Aabb3 { center: Point3::new(0.0f64, 1.0, 2.0), size: Vec3::new(2.0f64, 2.0, 4.0) };
+    let pl = Plane::from_abcd(0.0f64, 0.0, 1.0, -3.5);
+    let r = Ray2::new(Point2::new(1.0f64, 2.0), Vec2::new(1.0f64, 0.0));
+
+    assert_eq!(from_json::<Vec2<f64>>(to_json(&v)), v);
+    assert_eq!(from_json::<Mat4<f64>>(to_json(&m)), m);
+    assert_eq!(from_json::<Mat2x3<f64>>(to_json(&m23)), m23);
+    assert_eq!(from_json::<Aabb3<f64>>(to_json(&a)), a);
+    assert_eq!(from_json::<Plane<f64>>(to_json(&pl)), pl);
+    assert_eq!(from_json::<Ray2<f64>>(to_json(&r)), r);
+    assert_eq!(from_json::<Rad<f64>>(to_json(&rad(0.25f64))), rad(0.25f64));
+}
+
+#[test]
+fn test_le_bytes_layout() {
+    assert_eq!(to_le_bytes(&1.0f32), ~[0x00, 0x00, 0x80, 0x3f]);
+    assert_eq!(to_le_bytes(&-2.0f64), ~[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc0]);
+    assert_eq!(to_le_bytes(&Vec2::new(1.0f32, -2.0)),
+               ~[0x00, 0x00, 0x80, 0x3f, 0x00, 0x00, 0x00, 0xc0]);
+    assert_eq!(to_le_bytes(&Vec3::new(0.0f32, 0.0, 0.0)).len(), 12);
+    let m: Mat4<f64> = Mat4::identity();
+    assert_eq!(to_le_bytes(&m).len(), 128);
+    assert_eq!(to_le_bytes(&Sphere { center: Point3::new(0.0f32, 0.0, 0.0), radius: 1.0f32 }).len(), 16);
+}
+
+#[test]
+fn test_le_bytes_round_trip_f32() {
+    let v = Vec3::new(0.1f32, -2.0, 1.0e30);
+    let q = Quat::new(0.3f32, 0.1, -0.7, 0.2);
+    let m = Mat3x4::new(0.1f32, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0, 1.1, 1.2);
+    let a = Aabb2 { center: Point2::new(0.1f32, 0.2), size: Vec2::new(3.0f32, 4.0) };
+    let pl = Plane::from_abcd(0.6f32, 0.8, 0.0, -0.1);
+
+    assert_eq!(from_le_bytes::<Vec3<f32>>(to_le_bytes(&v)), Some(v));
+    assert_eq!(from_le_bytes::<Quat<f32>>(to_le_bytes(&q)), Some(q));
+    assert_eq!(from_le_bytes::<Mat3x4<f32>>(to_le_bytes(&m)), Some(m));
+    assert_eq!(from_le_bytes::<Aabb2<f32>>(to_le_bytes(&a)), Some(a));
+    assert_eq!(from_le_bytes::<Plane<f32>>(to_le_bytes(&pl)), Some(pl));
+    assert_eq!(from_le_bytes::<Rad<f32>>(to_le_bytes(&rad(0.1f32))), Some(rad(0.1f32)));
+}
+
+#[test]
+fn test_le_bytes_round_trip_f64() {
+    let p = Point3::new(0.1f64, -0.2, 1.0e300);
+    let m = Mat4::new(0.1f64, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8,
+                      0.9, 1.0, 1.1, 1.2, 1.3, 1.4, 1.5, 1.6);
+    let r = Ray3::new(Point3::new(0.1f64, 0.2, 0.3), Vec3::new(0.0f64, 1.0, 0.0));
+    let s = Sphere { center: Point3::new(0.1f64, 0.2, 0.3), radius: 0.7f64 };
+
+    assert_eq!(from_le_bytes::<Point3<f64>>(to_le_bytes(&p)), Some(p));
+    assert_eq!(from_le_bytes::<Mat4<f64>>(to_le_bytes(&m)), Some(m));
+    assert_eq!(from_le_bytes::<Ray3<f64>>(to_le_bytes(&r)), Some(r));
+    assert_eq!(from_le_bytes::<Sphere<f64>>(to_le_bytes(&s)), Some(s));
+    assert_eq!(from_le_bytes::<Deg<f64>>(to_le_bytes(&deg(0.1f64))), Some(deg(0.1f64)));
+}
+
+#[test]
+fn test_le_bytes_wrong_length() {
+    let bytes = to_le_bytes(&Vec3::new(1.0f32, 2.0, 3.0));
+    assert_eq!(from_le_bytes::<Vec3<f32>>(bytes.slice_to(8)), None);
+    assert_eq!(from_le_bytes::<Vec2<f32>>(bytes), None);
+    assert_eq!(from_le_bytes::<Vec3<f64>>(bytes), None);
+}
@@ -0,0 +1,118 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use extra::test::BenchHarness;
+
+use cgmath::approx::*;
+use cgmath::matrix::*;
+use cgmath::quaternion::*;
+use cgmath::simd::*;
+use cgmath::vector::*;
+
+static A: Mat4<f32> = Mat4 { x: Vec4 { x: 2.0, y: 0.5, z: -1.0, w: 0.0 },
+                             y: Vec4 { x: 0.0, y: 3.0, z:  1.0, w: 0.5 },
+                             z: Vec4 { x: 1.0, y: 0.0, z:  4.0, w: 0.0 },
+                             w: Vec4 { x: 5.0, y: -2.0, z: 1.5, w: 1.0 } };
+static B: Mat4<f32> = Mat4 { x: Vec4 { x: 1.0, y: 2.0, z: 3.0, w: 4.0 },
+                             y: Vec4 { x: 0.5, y: -1.0, z: 0.0, w: 2.0 },
+                             z: Vec4 { x: 0.0, y: 1.0, z: 1.0, w: -1.0 },
+                             w: Vec4 { x: 2.0, y: 0.0, z: 0.5, w: 1.0 } };
+static V: Vec4<f32> = Vec4 { x: 1.0, y: -2.0, z: 0.5, w: 1.0 };
+static W: Vec4<f32> = Vec4 { x: 0.25, y: 3.0, z: -1.0, w: 2.0 };
+static P: Quat<f32> = Quat { s: 0.5, v: Vec3 { x: -0.5, y: 0.5, z: 0.5 } };
+static Q: Quat<f32> = Quat { s: 1.0, v: Vec3 { x: 2.0, y: -3.0, z: 0.5 } };
+
+#[test]
+fn test_vec4() {
+    assert_approx_eq!(vec4_add(&V, &W), V.add_v(&W));
+    assert_approx_eq!(vec4_sub(&V, &W), V.sub_v(&W));
+    assert_approx_eq!(vec4_mul(&V, &W), V.mul_v(&W));
+    assert_approx_eq!(vec4_mul_s(&V, 1.5), V.mul_s(1.5));
+    assert_approx_eq!(vec4_dot(&V, &W), V.dot(&W));
+    assert_approx_eq!(vec4_normalize(&V), V.normalize());
+}
+
+#[test]
+fn test_quat() {
+    assert_approx_eq!(quat_mul_q(&P, &Q), P.mul_q(&Q));
+    assert_approx_eq!(quat_mul_q(&Q, &P), Q.mul_q(&P));
+    assert_approx_eq!(quat_dot(&P, &Q), P.dot(&Q));
+    assert_approx_eq!(quat_normalize(&Q), Q.normalize());
+}
+
+#[test]
+fn test_mat4() {
+    assert_approx_eq!(mat4_mul_v(&A, &V), A.mul_v(&V));
+    assert_approx_eq!(mat4_mul_m(&A, &B), A.mul_m(&B));
+    assert_approx_eq!(mat4_mul_m(&B, &A), B.mul_m(&A));
+}
+
+#[test]
+fn test_mat4_invert() {
+    assert_relative_eq!(mat4_invert(&A).unwrap(), A.invert().unwrap(), 1.0e-5f32, 1.0e-5f32);
+    assert_relative_eq!(mat4_invert(&B).unwrap(), B.invert().unwrap(), 1.0e-5f32, 1.0e-5f32);
+    assert_relative_eq!(mat4_mul_m(&A, &mat4_invert(&A).unwrap()), Mat4::identity(), 1.0e-5f32, 1.0e-5f32);
+
+    let singular = Mat4::from_cols(A.x, A.y, A.x.mul_s(2.0), A.w);
+    assert!(mat4_invert(&singular).is_none());
+}
+
+#[bench]
+fn bench_mat4_mul_m_scalar(b: &mut BenchHarness) {
+    let mut m = A;
+    do b.iter { m = m.mul_m(&B); }
+}
+
+#[bench]
+fn bench_mat4_mul_m_simd(b: &mut BenchHarness) {
+    let mut m = A;
+    do b.iter { m = mat4_mul_m(&m, &B); }
+}
+
+#[bench]
+fn bench_mat4_mul_v_scalar(b: &mut BenchHarness) {
+    let mut v = V;
+    do b.iter { v = A.mul_v(&v); }
+}
+
+#[bench]
+fn bench_mat4_mul_v_simd(b: &mut BenchHarness) {
+    let mut v = V;
+    do b.iter { v = mat4_mul_v(&A, &v); }
+}
+
+#[bench]
+fn bench_mat4_invert_scalar(b: &mut BenchHarness) {
+    let mut m = A;
+    do b.iter { m = m.invert().unwrap(); }
+}
+
+#[bench]
+fn bench_mat4_invert_simd(b: &mut BenchHarness) {
+    let mut m = A;
+    do b.iter { m = mat4_invert(&m).unwrap(); }
+}
+
+#[bench]
+fn bench_quat_mul_q_scalar(b: &mut BenchHarness) {
+    let mut q = P;
+    do b.iter { q = q.mul_q(&P); }
+}
+
+#[bench]
+fn bench_quat_mul_q_simd(b: &mut BenchHarness) {
+    let mut q = P;
+    do b.iter { q = quat_mul_q(&q, &P); }
+}
@@ -0,0 +1,108 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sys::{size_of, min_align_of};
+
+use cgmath::matrix::*;
+use cgmath::point::*;
+use cgmath::quaternion::*;
+use cgmath::vector::*;
+
+fn check_layout<S, T>(n: uint) {
+    assert_eq!(size_of::<T>(), n * size_of::<S>());
+    assert_eq!(min_align_of::<T>(), min_align_of::<S>());
+}
+
+fn check_layouts<S>() {
+    check_layout::<S, Vec2<S>>(2);
+    check_layout::<S, Vec3<S>>(3);
+    check_layout::<S, Vec4<S>>(4);
+    check_layout::<S, Point2<S>>(2);
+    check_layout::<S, Point3<S>>(3);
+    check_layout::<S, Quat<S>>(4);
+    check_layout::<S, Mat2<S>>(4);
+    check_layout::<S, Mat3<S>>(9);
+    check_layout::<S, Mat4<S>>(16);
+    check_layout::<S, Mat2x3<S>>(6);
+    check_layout::<S, Mat2x4<S>>(8);
+    check_layout::<S, Mat3x2<S>>(6);
+    check_layout::<S, Mat3x4<S>>(12);
+    check_layout::<S, Mat4x2<S>>(8);
+    check_layout::<S, Mat4x3<S>>(12);
+}
+
+#[test]
+fn test_layout() {
+    // the casts rely on every type being exactly its components, with the
+    // same alignment as a single component
+    check_layouts::<u8>();
+    check_layouts::<i16>();
+    check_layouts::<f32>();
+    check_layouts::<f64>();
+}
+
+#[test]
+fn test_as_scalars() {
+    let vs = &[Vec3::new(1.0f32, 2.0, 3.0), Vec3::new(4.0f32, 5.0, 6.0)];
+    assert_eq!(Vec3::slice_as_scalars(vs).to_owned(), ~[1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    let qs = &[Quat::new(1.0f64, 2.0, 3.0, 4.0)];
+    assert_eq!(Quat::slice_as_scalars(qs).to_owned(), ~[1.0f64, 2.0, 3.0, 4.0]);
+
+    let ms = &[Mat2::new(1.0f32, 2.0, 3.0, 4.0)];
+    assert_eq!(Mat2::slice_as_scalars(ms).to_owned(), ~[1.0f32, 2.0, 3.0, 4.0]);
+
+    let ms: &[Mat4<f32>] = &[Mat4::identity(), Mat4::identity()];
+    assert_eq!(Mat4::slice_as_scalars(ms).len(), 32);
+
+    let empty: &[Point3<f32>] = &[];
+    assert!(Point3::slice_as_scalars(empty).is_empty());
+}
+
+#[test]
+fn test_from_scalars() {
+    let xs = &[1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+
+    let vs = Vec3::slice_from_scalars(xs).unwrap();
+    assert_eq!(vs.to_owned(), ~[Vec3::new(1.0f32, 2.0, 3.0), Vec3::new(4.0f32, 5.0, 6.0)]);
+
+    let ps = Point2::slice_from_scalars(xs).unwrap();
+    assert_eq!(ps.to_owned(), ~[Point2::new(1.0f32, 2.0), Point2::new(3.0f32, 4.0), Point2::new(5.0f32, 6.0)]);
+
+    let ms = Mat2x3::slice_from_scalars(xs).unwrap();
+    assert_eq!(ms.to_owned(), ~[Mat2x3::new(1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0)]);
+
+    assert!(Vec4::slice_from_scalars(xs).is_none());
+    assert!(Mat4::slice_from_scalars(xs).is_none());
+}
+
+#[test]
+fn test_mut_scalars() {
+    let vs = &mut [Vec2::new(1.0f64, 2.0), Vec2::new(3.0f64, 4.0)];
+    {
+        let xs = Vec2::slice_as_mut_scalars(vs);
+        xs[1] = 20.0;
+        xs[3] = 40.0;
+    }
+    assert_eq!(vs.to_owned(), ~[Vec2::new(1.0f64, 20.0), Vec2::new(3.0f64, 40.0)]);
+
+    let xs = &mut [1.0f32, 0.0, 0.0, 0.0];
+    {
+        let qs = Quat::slice_from_mut_scalars(xs).unwrap();
+        qs[0].v.y = 1.0;
+    }
+    assert_eq!(xs.to_owned(), ~[1.0f32, 0.0, 1.0, 0.0]);
+    assert!(Vec3::slice_from_mut_scalars(xs).is_none());
+}
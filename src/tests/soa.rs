@@ -0,0 +1,194 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use cgmath::matrix::*;
+use cgmath::point::*;
+use cgmath::soa::*;
+use cgmath::vector::*;
+
+fn vs() -> ~[Vec3<f64>] {
+    ~[Vec3::new(1.0f64, 0.0, 0.0),
+      Vec3::new(0.0f64, 2.0, 0.0),
+      Vec3::new(3.0f64, 4.0, 0.0)]
+}
+
+fn ws() -> ~[Vec3<f64>] {
+    ~[Vec3::new(0.0f64, 1.0, 0.0),
+      Vec3::new(0.0f64, 0.0, 1.0),
+      Vec3::new(1.0f64, -1.0, 2.0)]
+}
+
+#[test]
+fn test_conversion() {
+    let a = Vec3xN::from_vecs(vs());
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.xs().to_owned(), ~[1.0, 0.0, 3.0]);
+    assert_eq!(a.ys().to_owned(), ~[0.0, 2.0, 4.0]);
+    assert_eq!(a.zs().to_owned(), ~[0.0, 0.0, 0.0]);
+    assert_eq!(a.get(2), Vec3::new(3.0, 4.0, 0.0));
+    assert_eq!(a.to_vecs(), vs());
+
+    let ps = ~[Point3::new(1.0f64, 2.0, 3.0), Point3::new(-1.0f64, 0.5, 0.0)];
+    assert_eq!(Vec3xN::from_points(ps).to_points(), ps);
+
+    let mut b = Vec3xN::new();
+    assert!(b.is_empty());
+    b.push(&Vec3::new(1.0f64, 2.0, 3.0));
+    b.push(&Vec3::new(4.0f64, 5.0, 6.0));
+    b.set(0, &Vec3::new(0.0f64, 0.0, 0.0));
+    assert_eq!(b.to_vecs(), ~[Vec3::new(0.0, 0.0, 0.0), Vec3::new(4.0, 5.0, 6.0)]);
+}
+
+#[test]
+fn test_arithmetic() {
+    let (vs, ws) = (vs(), ws());
+    let (a, b) = (Vec3xN::from_vecs(vs), Vec3xN::from_vecs(ws));
+
+    let sum = a.add_v(&b);
+    let diff = a.sub_v(&b);
+    let scaled = a.mul_s(2.0);
+    let cross = a.cross(&b);
+    let dot = a.dot(&b);
+    for i in range(0u, 3) {
+        assert_eq!(sum.get(i), vs[i].add_v(&ws[i]));
+        assert_eq!(diff.get(i), vs[i].sub_v(&ws[i]));
+        assert_eq!(scaled.get(i), vs[i].mul_s(2.0));
+        assert_eq!(cross.get(i), vs[i].cross(&ws[i]));
+        assert_eq!(dot[i], vs[i].dot(&ws[i]));
+    }
+}
+
+#[test]
+fn test_length() {
+    let a = Vec3xN::from_vecs(vs());
+    assert_eq!(a.length2(), ~[1.0, 4.0, 25.0]);
+    assert_eq!(a.length(), ~[1.0, 2.0, 5.0]);
+
+    let n = a.normalize();
+    assert_approx_eq!(n.get(0), Vec3::new(1.0, 0.0, 0.0));
+    assert_approx_eq!(n.get(1), Vec3::new(0.0, 1.0, 0.0));
+    assert_approx_eq!(n.get(2), Vec3::new(0.6, 0.8, 0.0));
+}
+
+#[test]
+fn test_transform() {
+    let m = Mat4::new(0.0f64, 1.0, 0.0, 0.0,
+                      -1.0, 0.0, 0.0, 0.0,
+                      0.0, 0.0, 2.0, 0.0,
+                      10.0, 20.0, 30.0, 1.0);
+    let a = Vec3xN::from_vecs(ws());
+
+    let points = a.transform_points(&m);
+    let vectors = a.transform_vectors(&m);
+    for (i, w) in ws().iter().enumerate() {
+        let (p, v) = (points.get(i), vectors.get(i));
+        assert_eq!(Vec4::new(p.x, p.y, p.z, 1.0), m.mul_v(&Vec4::new(w.x, w.y, w.z, 1.0)));
+        assert_eq!(Vec4::new(v.x, v.y, v.z, 0.0), m.mul_v(&Vec4::new(w.x, w.y, w.z, 0.0)));
+    }
+}
+
+#[test]
+#[should_fail]
+fn test_length_mismatch() {
+    let a = Vec3xN::from_vecs(vs());
+    let b = Vec3xN::from_vecs(ws().slice_to(2));
+    a.add_v(&b);
+}
+
+fn vs4() -> ~[Vec3<f64>] {
+    ~[Vec3::new(1.0f64, 0.0, 0.0),
+      Vec3::new(0.0f64, 2.0, 0.0),
+      Vec3::new(3.0f64, 4.0, 0.0),
+      Vec3::new(0.0f64, 0.0, -0.5)]
+}
+
+fn ws4() -> ~[Vec3<f64>] {
+    ~[Vec3::new(0.0f64, 1.0, 0.0),
+      Vec3::new(0.0f64, 0.0, 1.0),
+      Vec3::new(1.0f64, -1.0, 2.0),
+      Vec3::new(2.0f64, 3.0, 4.0)]
+}
+
+#[test]
+fn test_x4_conversion() {
+    let a = Vec3x4::from_vecs(vs4());
+    assert_eq!(a.x, Vec4::new(1.0, 0.0, 3.0, 0.0));
+    assert_eq!(a.z, Vec4::new(0.0, 0.0, 0.0, -0.5));
+    assert_eq!(a.get(2), Vec3::new(3.0, 4.0, 0.0));
+    assert_eq!(a.to_vecs(), vs4());
+
+    let ps = ~[Point3::new(1.0f64, 2.0, 3.0), Point3::new(-1.0f64, 0.5, 0.0),
+               Point3::new(0.0f64, 0.0, 0.0), Point3::new(4.0f64, 5.0, 6.0)];
+    assert_eq!(Vec3x4::from_points(ps).to_points(), ps);
+
+    let mut b = Vec3x4::splat(&Vec3::new(1.0f64, 2.0, 3.0));
+    b.set(3, &Vec3::new(0.0f64, 0.0, 0.0));
+    assert_eq!(b.get(0), Vec3::new(1.0, 2.0, 3.0));
+    assert_eq!(b.get(3), Vec3::new(0.0, 0.0, 0.0));
+}
+
+#[test]
+fn test_x4_arithmetic() {
+    let (vs, ws) = (vs4(), ws4());
+    let (a, b) = (Vec3x4::from_vecs(vs), Vec3x4::from_vecs(ws));
+
+    let sum = a.add_v(&b);
+    let diff = a.sub_v(&b);
+    let scaled = a.mul_s(2.0);
+    let cross = a.cross(&b);
+    let dot = a.dot(&b);
+    for i in range(0u, 4) {
+        assert_eq!(sum.get(i), vs[i].add_v(&ws[i]));
+        assert_eq!(diff.get(i), vs[i].sub_v(&ws[i]));
+        assert_eq!(scaled.get(i), vs[i].mul_s(2.0));
+        assert_eq!(cross.get(i), vs[i].cross(&ws[i]));
+    }
+    assert_eq!(dot, Vec4::new(vs[0].dot(&ws[0]), vs[1].dot(&ws[1]),
+                              vs[2].dot(&ws[2]), vs[3].dot(&ws[3])));
+}
+
+#[test]
+fn test_x4_length() {
+    let a = Vec3x4::from_vecs(vs4());
+    assert_eq!(a.length2(), Vec4::new(1.0, 4.0, 25.0, 0.25));
+    assert_eq!(a.length(), Vec4::new(1.0, 2.0, 5.0, 0.5));
+
+    let n = a.normalize();
+    assert_approx_eq!(n.get(2), Vec3::new(0.6, 0.8, 0.0));
+    assert_approx_eq!(n.get(3), Vec3::new(0.0, 0.0, -1.0));
+}
+
+#[test]
+fn test_x4_transform() {
+    let m = Mat4::new(0.0f64, 1.0, 0.0, 0.0,
+                      -1.0, 0.0, 0.0, 0.0,
+                      0.0, 0.0, 2.0, 0.0,
+                      10.0, 20.0, 30.0, 1.0);
+    let a = Vec3x4::from_vecs(ws4());
+
+    let points = a.transform_points(&m);
+    let vectors = a.transform_vectors(&m);
+    for (i, w) in ws4().iter().enumerate() {
+        let (p, v) = (points.get(i), vectors.get(i));
+        assert_eq!(Vec4::new(p.x, p.y, p.z, 1.0), m.mul_v(&Vec4::new(w.x, w.y, w.z, 1.0)));
+        assert_eq!(Vec4::new(v.x, v.y, v.z, 0.0), m.mul_v(&Vec4::new(w.x, w.y, w.z, 0.0)));
+    }
+}
+
+#[test]
+#[should_fail]
+fn test_x4_wrong_length() {
+    Vec3x4::from_vecs(vs());
+}
@@ -13,25 +13,44 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 #[feature(globs)];
+#[feature(macro_rules)];
 
 extern mod cgmath;
+extern mod extra;
 
+// Macros cannot be exported from a crate, so the assertion macros are
+// brought in by including the module that defines them.
+#[path = "../cgmath/approx.rs"]
+mod approx_macros;
+
+pub mod approx;
 // pub mod array;
+pub mod buffer;
+pub mod dual_quaternion;
+pub mod format;
 pub mod matrix;
-// pub mod quaternion;
+pub mod quaternion;
+pub mod simd;
+pub mod slice;
+pub mod soa;
 pub mod vector;
 
 pub mod angle;
 pub mod plane;
 // pub mod point;
 // pub mod ray;
-// pub mod rotation;
+pub mod rotation;
 
-// pub mod projection;
+pub mod cascade;
+pub mod projection;
 
 // pub mod aabb;
 // pub mod cylinder;
 // pub mod frustum;
 // pub mod intersect;
 // pub mod obb;
+pub mod polyhedron;
 // pub mod sphere;
+
+#[cfg(serialize)]
+pub mod serialize;